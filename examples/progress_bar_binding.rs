@@ -0,0 +1,65 @@
+//! This example illustrates how to bind a progress bar to a resource,
+//! so you don't need to write a system that copies the value into `Progress`.
+
+use bevy::prelude::*;
+
+use bevy_slimy_widgets::progress_bar::{
+    BindProgress, ProgressBarSizeAnimation, ProgressBindingAppExt,
+};
+use bevy_slimy_widgets::{ProgressBarBundle, SlimyWidgetsPlugin};
+
+/// Loading percent that some loading system updates
+#[derive(Clone, Copy, Default)]
+struct LoadingPercent(f32);
+
+impl From<LoadingPercent> for f32 {
+    fn from(percent: LoadingPercent) -> Self {
+        percent.0
+    }
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(SlimyWidgetsPlugin)
+        .init_resource::<LoadingPercent>()
+        .bind_progress::<LoadingPercent>()
+        .add_startup_system(setup)
+        .add_system(fake_loading_system)
+        .run();
+}
+
+/// Pretends to load something for 5 seconds, then starts again
+fn fake_loading_system(time: Res<Time>, mut loading: ResMut<LoadingPercent>) {
+    loading.0 = (loading.0 + time.delta_seconds() * 20.0) % 100.0;
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn_bundle(Camera2dBundle::default());
+
+    // track
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Px(270.0), Val::Px(50.0)),
+                margin: UiRect::all(Val::Auto),
+                border: UiRect::all(Val::Px(7.0)),
+                ..Default::default()
+            },
+            color: Color::BLACK.into(),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(ProgressBarBundle {
+                    style: Style {
+                        size: Size::new(Val::Auto, Val::Percent(100.0)),
+                        ..Default::default()
+                    },
+                    color: Color::GREEN.into(),
+                    ..Default::default()
+                })
+                .insert(ProgressBarSizeAnimation::Width)
+                .insert(BindProgress::<LoadingPercent>::default());
+        });
+}
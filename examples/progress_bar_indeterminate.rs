@@ -0,0 +1,61 @@
+//! This example illustrates an indeterminate progress bar, for when the amount of work
+//! isn't known yet. After a few seconds the bar starts showing real progress.
+
+use bevy::prelude::*;
+
+use bevy_slimy_widgets::progress_bar::{IndeterminateProgress, Progress, ProgressBarSizeAnimation};
+use bevy_slimy_widgets::{ProgressBarBundle, SlimyWidgetsPlugin};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(SlimyWidgetsPlugin)
+        .add_startup_system(setup)
+        .add_system(fake_loading_system)
+        .run();
+}
+
+/// Pretends to connect to a server for 3 seconds, then to download something for 5 seconds
+fn fake_loading_system(time: Res<Time>, mut query: Query<&mut Progress>) {
+    let elapsed = time.seconds_since_startup() as f32;
+    if elapsed < 3.0 {
+        return;
+    }
+    let percent = ((elapsed - 3.0) * 20.0).min(100.0);
+    for mut progress in query.iter_mut() {
+        if (**progress - percent).abs() > f32::EPSILON {
+            // the first change switches the bar back to determinate
+            progress.set(percent);
+        }
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn_bundle(Camera2dBundle::default());
+
+    // track
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Px(270.0), Val::Px(50.0)),
+                margin: UiRect::all(Val::Auto),
+                border: UiRect::all(Val::Px(7.0)),
+                ..Default::default()
+            },
+            color: Color::BLACK.into(),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(ProgressBarBundle {
+                    style: Style {
+                        size: Size::new(Val::Auto, Val::Percent(100.0)),
+                        ..Default::default()
+                    },
+                    color: Color::GREEN.into(),
+                    ..Default::default()
+                })
+                .insert(ProgressBarSizeAnimation::Width)
+                .insert(IndeterminateProgress { speed: 1.5 });
+        });
+}
@@ -0,0 +1,46 @@
+//! This example illustrates a radial progress indicator for loading screens.
+//! bevy_ui can't draw arcs, so the progress is displayed as a ring of slices.
+
+use bevy::prelude::*;
+
+use bevy_slimy_widgets::progress_bar::{Progress, RadialProgressSegments};
+use bevy_slimy_widgets::{ProgressBarBundle, SlimyWidgetsPlugin};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(SlimyWidgetsPlugin)
+        .add_startup_system(setup)
+        .add_system(fake_loading_system)
+        .run();
+}
+
+/// Pretends to load something for 4 seconds, then starts over
+fn fake_loading_system(time: Res<Time>, mut query: Query<&mut Progress>) {
+    let percent = (time.seconds_since_startup() as f32 % 5.0 * 25.0).min(100.0);
+    for mut progress in query.iter_mut() {
+        if (**progress - percent).abs() > f32::EPSILON {
+            progress.set(percent);
+        }
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn_bundle(Camera2dBundle::default());
+
+    commands
+        .spawn_bundle(ProgressBarBundle {
+            style: Style {
+                size: Size::new(Val::Px(120.0), Val::Px(120.0)),
+                margin: UiRect::all(Val::Auto),
+                ..Default::default()
+            },
+            color: Color::NONE.into(),
+            ..Default::default()
+        })
+        .insert(RadialProgressSegments {
+            count: 16,
+            filled_color: Color::ORANGE.into(),
+            ..Default::default()
+        });
+}
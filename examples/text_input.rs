@@ -2,7 +2,9 @@
 
 use bevy::prelude::*;
 
-use bevy_slimy_widgets::text_input::{DefaultConstrains, TextCursorStyle, TextInputConstrains};
+use bevy_slimy_widgets::text_input::{
+    DefaultConstrains, InputMode, TextCursorStyle, TextInputConstrains,
+};
 use bevy_slimy_widgets::{SlimyWidgetsPlugin, TextInputBundle};
 
 fn main() {
@@ -58,10 +60,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                 }
                 .into(),
                 color: Color::DARK_GRAY.into(),
-                constrains: TextInputConstrains(vec![
-                    Box::new(DefaultConstrains::MaxLength(13)),
-                    Box::new(DefaultConstrains::DisallowedCharacters(vec!['\n'])),
-                ]),
+                constrains: TextInputConstrains::default().with(DefaultConstrains::MaxLength(13)),
                 cursor: TextCursorStyle::default(
                     24.0,
                     Color::WHITE.into(),
@@ -108,6 +107,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
                 .into(),
                 color: Color::DARK_GRAY.into(),
                 constrains: TextInputConstrains(Vec::new()),
+                mode: InputMode::Multiline,
                 cursor: TextCursorStyle::default(
                     16.0,
                     Color::WHITE.into(),
@@ -0,0 +1,97 @@
+//! This example shows the cursor shapes of a text input. Press F1 to switch between them
+
+use bevy::prelude::*;
+
+use bevy_slimy_widgets::text_input::{CursorShape, TextCursorStyle};
+use bevy_slimy_widgets::{SlimyWidgetsPlugin, TextInputBundle};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(SlimyWidgetsPlugin)
+        .add_startup_system(setup)
+        .add_system(switch_shape_system)
+        .run();
+}
+
+#[derive(Component)]
+struct ShapeText;
+
+fn switch_shape_system(
+    keys: Res<Input<KeyCode>>,
+    mut shapes: Query<&mut CursorShape>,
+    mut texts: Query<&mut Text, With<ShapeText>>,
+) {
+    if !keys.just_pressed(KeyCode::F1) {
+        return;
+    }
+    for mut shape in shapes.iter_mut() {
+        *shape = match *shape {
+            CursorShape::Bar => CursorShape::Block,
+            CursorShape::Block => CursorShape::Underline,
+            CursorShape::Underline => CursorShape::Bar,
+        };
+        for mut text in texts.iter_mut() {
+            text.sections[0].value = format!("Cursor shape: {:?} (F1 to switch)", *shape);
+        }
+    }
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn_bundle(Camera2dBundle::default());
+
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    // root node
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            color: Color::GRAY.into(),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(TextBundle {
+                    text: Text::from_section(
+                        "Cursor shape: Bar (F1 to switch)",
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 20.0,
+                            color: Color::WHITE,
+                        },
+                    ),
+                    ..Default::default()
+                })
+                .insert(ShapeText);
+
+            parent
+                .spawn_bundle(TextInputBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(400.0), Val::Px(30.0)),
+                        margin: UiRect::all(Val::Px(10.0)),
+                        ..Default::default()
+                    },
+                    text_style: TextStyle {
+                        font,
+                        font_size: 24.0,
+                        color: Color::WHITE,
+                    }
+                    .into(),
+                    color: Color::DARK_GRAY.into(),
+                    // translucent, so the block doesn't hide the character
+                    cursor: TextCursorStyle::default(
+                        24.0,
+                        Color::rgba(1.0, 1.0, 1.0, 0.6).into(),
+                        UiRect::all(Val::Undefined),
+                        TextAlignment::default(),
+                    ),
+                    ..Default::default()
+                })
+                .insert(CursorShape::Bar);
+        });
+}
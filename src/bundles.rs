@@ -2,15 +2,18 @@ use bevy::prelude::*;
 use bevy::ui::FocusPolicy;
 
 use crate::progress_bar::Progress;
+use crate::segmented_input::SegmentedInput;
 use crate::text_input::{
-    CursorBlinkingInterval, DefaultConstrains, InputTextAlignment, InputTextStyle, PlaceholderText,
-    TextCursorStyle, TextInputConstrains, TextInputFocus, TextInputTargetSize, TextInputValue,
+    CursorBlinkingInterval, InputMode, InputTextAlignment, InputTextStyle, PlaceholderText,
+    TextCursorStyle, TextInputConstrains, TextInputFocus, TextInputSelection, TextInputTargetSize,
+    TextInputValue,
 };
 
 /// A UI node that is a progress bar
 ///
 /// In order to work as expected, you must add the animation marker component
 /// to define how to display progress. Use built-in [`ProgressBarSizeAnimation`](crate::progress_bar::ProgressBarSizeAnimation)
+/// and [`ProgressBarColorAnimation`](crate::progress_bar::ProgressBarColorAnimation)
 /// or implement your own animation:
 ///
 /// ```rust
@@ -45,7 +48,7 @@ use crate::text_input::{
 ///     }
 /// }
 /// ```
-#[derive(Bundle, Clone, Debug)]
+#[derive(Bundle, Clone, Debug, Default)]
 pub struct ProgressBarBundle {
     /// Describes the size of the node
     pub node: Node,
@@ -67,22 +70,6 @@ pub struct ProgressBarBundle {
     pub visibility: Visibility,
 }
 
-impl Default for ProgressBarBundle {
-    fn default() -> Self {
-        ProgressBarBundle {
-            progress: Progress::empty(),
-            focus_policy: Default::default(),
-            node: Default::default(),
-            style: Default::default(),
-            color: Default::default(),
-            image: Default::default(),
-            transform: Default::default(),
-            global_transform: Default::default(),
-            visibility: Default::default(),
-        }
-    }
-}
-
 /// A text input field. It has a child with [`TextCursor`] component
 /// when focused, and is focused on click.
 #[derive(Bundle)]
@@ -113,9 +100,13 @@ pub struct TextInputBundle {
     pub text_alignment: InputTextAlignment,
     /// The character won't be added to the input if any of these returns false
     pub constrains: TextInputConstrains,
+    /// Whether Enter inserts a new line. Default is [`InputMode::SingleLine`]
+    pub mode: InputMode,
     /// Whether the text input is focused or not.
     /// If the text input is focused, it will hold cursor index
     pub focus: TextInputFocus,
+    /// The selected text, from the anchor to the cursor
+    pub selection: TextInputSelection,
     /// A blinking thing that appears when you focus on a text input.
     /// A bundle that will be spawned with [`TextCursor`] component
     pub cursor: TextCursorStyle,
@@ -142,10 +133,10 @@ impl Default for TextInputBundle {
             placeholder: Default::default(),
             text_style: Default::default(),
             text_alignment: Default::default(),
-            constrains: TextInputConstrains(vec![Box::new(
-                DefaultConstrains::DisallowedCharacters(vec!['\n']),
-            )]),
+            constrains: Default::default(),
+            mode: Default::default(),
             focus: Default::default(),
+            selection: Default::default(),
             cursor: TextCursorStyle::default(
                 TextStyle::default().font_size,
                 TextStyle::default().color.into(),
@@ -158,3 +149,86 @@ impl Default for TextInputBundle {
         }
     }
 }
+
+/// A PIN/OTP entry field that displays its value across [`SegmentedInput::cells`] boxes.
+/// The cell with the cursor is highlighted instead of showing a blinking cursor.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_slimy_widgets::SegmentedInputBundle;
+/// # use bevy_slimy_widgets::segmented_input::SegmentedInput;
+/// # use bevy_slimy_widgets::text_input::{DefaultConstrains, TextInputConstrains};
+///
+/// fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     commands.spawn_bundle(SegmentedInputBundle {
+///         segmented: SegmentedInput::new(6),
+///         text_style: TextStyle {
+///             font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+///             font_size: 24.0,
+///             color: Color::WHITE,
+///         }
+///         .into(),
+///         constrains: TextInputConstrains::default().with(DefaultConstrains::AllowedCharacters(
+///             ('0'..='9').collect(),
+///         )),
+///         ..Default::default()
+///     });
+/// }
+/// ```
+#[derive(Bundle)]
+pub struct SegmentedInputBundle {
+    /// Describes the size of the node
+    pub node: Node,
+    /// Describes the style including flexbox settings
+    pub style: Style,
+    /// Describes whether and how the field has been interacted with by the input
+    pub interaction: Interaction,
+    /// Whether this node should block interaction with lower nodes
+    pub focus_policy: FocusPolicy,
+    /// The color of the node
+    pub color: UiColor,
+    /// The image of the node
+    pub image: UiImage,
+    /// The transform of the node
+    pub transform: Transform,
+    /// The global transform of the node
+    pub global_transform: GlobalTransform,
+    /// Describes the visibility properties of the node
+    pub visibility: Visibility,
+    /// Number of cells and their look
+    pub segmented: SegmentedInput,
+    /// Style of the characters in the cells
+    pub text_style: InputTextStyle,
+    /// The character won't be added to the input if any of these returns false.
+    /// The length is additionally limited to the number of cells
+    pub constrains: TextInputConstrains,
+    /// Enter doesn't insert new lines in a segmented input
+    pub mode: InputMode,
+    /// Whether the field is focused or not.
+    /// If the field is focused, it will hold cursor index
+    pub focus: TextInputFocus,
+    /// Field's value, text that is typed in here
+    pub value: TextInputValue,
+}
+
+impl Default for SegmentedInputBundle {
+    fn default() -> Self {
+        Self {
+            node: Default::default(),
+            style: Default::default(),
+            interaction: Default::default(),
+            focus_policy: Default::default(),
+            color: Color::NONE.into(),
+            image: Default::default(),
+            transform: Default::default(),
+            global_transform: Default::default(),
+            visibility: Default::default(),
+            segmented: Default::default(),
+            text_style: Default::default(),
+            constrains: Default::default(),
+            mode: InputMode::SingleLine,
+            focus: Default::default(),
+            value: Default::default(),
+        }
+    }
+}
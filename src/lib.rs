@@ -1,63 +1,431 @@
 #![cfg_attr(not(debug_assertions), deny(missing_docs))]
 #![deny(rustdoc::broken_intra_doc_links)]
 #![doc = include_str!("../README.md")]
-#![allow(clippy::type_complexity)]
+#![allow(clippy::type_complexity, clippy::too_many_arguments)]
 
+use bevy::ecs::schedule::ShouldRun;
 use bevy::prelude::*;
 
 pub use bundles::*;
+pub use query::*;
 pub use widgets::*;
 
+use crate::form::{form_submit_system, FormSubmitted, SubmitForm};
 use crate::text_input::{
-    text_input_blink_cursor_system, text_input_create_system, text_input_focus_on_click_system,
-    text_input_font_decrease_system, text_input_move_cursor_system, text_input_system,
-    text_input_unfocus_system, text_input_update_system,
+    glyph_calculator_cache_system, text_input_adornment_create_system, text_input_adornment_system,
+    text_input_auto_grow_system, text_input_auto_width_system, text_input_blink_cursor_system,
+    text_input_changed_event_system, text_input_clamp_cursor_system,
+    text_input_click_padding_create_system, text_input_click_padding_system,
+    text_input_counter_create_system, text_input_counter_system, text_input_create_system,
+    text_input_cursor_glide_system, text_input_drag_system, text_input_file_drop_system,
+    text_input_focus_event_system, text_input_focus_on_click_system, text_input_focus_style_system,
+    text_input_font_decrease_system, text_input_mode_system, text_input_move_cursor_system,
+    text_input_padding_system, text_input_placeholder_focus_system, text_input_reset_system,
+    text_input_scroll_system, text_input_scrollbar_create_system,
+    text_input_selection_highlight_system, text_input_status_system, text_input_system,
+    text_input_tab_focus_system, text_input_unfocus_system, text_input_update_system,
+    GlyphCalculatorCache, KeyRepeatConfig, MultiClickInterval, ResetTextInput, TextInputBlurred,
+    TextInputChanged, TextInputClipboard, TextInputFocused, TextInputKeybinds, TextInputRejected,
+    TextInputSubmit, TextInputTruncated,
+};
+use crate::typewriter::{typewriter_reveal_system, TypewriterFinished};
+use crate::validation::text_input_validation_reset_system;
+use crate::widgets::progress_bar::{
+    progress_bar_buffered_create_system, progress_bar_color_animation_system,
+    progress_bar_indeterminate_system, progress_bar_label_create_system, progress_bar_label_system,
+    progress_bar_segments_create_system, progress_bar_segments_system,
+    progress_bar_size_animation_system, progress_bar_throttle_system, progress_complete_system,
+    progress_rate_system, progress_reset_system, radial_progress_create_system,
+    radial_progress_system, ProgressComplete, ResetProgress,
+};
+use crate::widgets::segmented_input::{
+    segmented_input_create_system, segmented_input_update_system,
 };
-use crate::widgets::progress_bar::progress_bar_size_animation_system;
 
 mod bundles;
+mod query;
 mod widgets;
 
-/// A plugin struct. Use this with [`App::add_plugin()`]
+/// A plugin struct. Use this with [`App::add_plugin()`].
+/// Adds [`ProgressBarPlugin`] and [`TextInputPlugin`], add only one of them instead
+/// if you don't use the other widgets
 pub struct SlimyWidgetsPlugin;
 
 impl Plugin for SlimyWidgetsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system(
-            progress_bar_size_animation_system.label(SystemLabels::ProgressBarSizeAnimation),
-        )
-        .add_system(
-            text_input_unfocus_system
-                .label(SystemLabels::TextInputUnfocus)
-                .before(SystemLabels::TextInputFocusOnClick),
-        )
-        .add_system(text_input_focus_on_click_system.label(SystemLabels::TextInputFocusOnClick))
-        .add_system(
-            text_input_move_cursor_system
-                .label(SystemLabels::TextInputMoveCursor)
-                .after(SystemLabels::TextInputFontDecrease),
-        )
-        .add_system(text_input_blink_cursor_system.label(SystemLabels::TextInputBlinkCursor))
-        .add_system(text_input_create_system.label(SystemLabels::TextInputCreate))
-        .add_system(
-            text_input_update_system
-                .label(SystemLabels::TextInputUpdate)
-                .after(SystemLabels::TextInputCreate),
+        app.add_plugin(ProgressBarPlugin)
+            .add_plugin(TextInputPlugin);
+    }
+}
+
+/// Registers the systems and events of [`ProgressBarBundle`] and its components
+pub struct ProgressBarPlugin;
+
+impl Plugin for ProgressBarPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::new()
+                .label(SlimyWidgetsSet::ProgressBar)
+                .with_run_criteria(widgets_active_run_criteria)
+                .with_system(
+                    progress_bar_size_animation_system
+                        .label(SystemLabels::ProgressBarSizeAnimation)
+                        .after(SystemLabels::ProgressBarThrottle),
+                )
+                .with_system(
+                    progress_bar_throttle_system
+                        .label(SystemLabels::ProgressBarThrottle)
+                        .after(SystemLabels::ProgressRate),
+                )
+                .with_system(progress_rate_system.label(SystemLabels::ProgressRate))
+                .with_system(
+                    progress_reset_system
+                        .label(SystemLabels::ProgressReset)
+                        .before(SystemLabels::ProgressRate),
+                )
+                .with_system(
+                    progress_bar_color_animation_system
+                        .label(SystemLabels::ProgressBarColorAnimation)
+                        .after(SystemLabels::ProgressBarThrottle),
+                )
+                .with_system(
+                    progress_bar_indeterminate_system
+                        .label(SystemLabels::ProgressBarIndeterminate)
+                        .after(SystemLabels::ProgressRate)
+                        .after(SystemLabels::ProgressReset),
+                )
+                .with_system(
+                    progress_bar_label_create_system.label(SystemLabels::ProgressBarLabelCreate),
+                )
+                .with_system(
+                    progress_bar_label_system
+                        .label(SystemLabels::ProgressBarLabel)
+                        .after(SystemLabels::ProgressBarLabelCreate)
+                        .after(SystemLabels::ProgressBarThrottle)
+                        .after(SystemLabels::ProgressRate)
+                        .after(SystemLabels::ProgressReset),
+                )
+                .with_system(
+                    progress_complete_system
+                        .label(SystemLabels::ProgressComplete)
+                        .after(SystemLabels::ProgressRate)
+                        .after(SystemLabels::ProgressReset),
+                )
+                .with_system(
+                    progress_bar_segments_create_system
+                        .label(SystemLabels::ProgressBarSegmentsCreate),
+                )
+                .with_system(
+                    progress_bar_segments_system
+                        .label(SystemLabels::ProgressBarSegments)
+                        .after(SystemLabels::ProgressBarSegmentsCreate)
+                        .after(SystemLabels::ProgressRate)
+                        .after(SystemLabels::ProgressReset),
+                )
+                .with_system(
+                    progress_bar_buffered_create_system
+                        .label(SystemLabels::ProgressBarBufferedCreate)
+                        .before(SystemLabels::ProgressBarSizeAnimation),
+                )
+                .with_system(
+                    radial_progress_create_system.label(SystemLabels::RadialProgressCreate),
+                )
+                .with_system(
+                    radial_progress_system
+                        .label(SystemLabels::RadialProgress)
+                        .after(SystemLabels::RadialProgressCreate)
+                        .after(SystemLabels::ProgressRate)
+                        .after(SystemLabels::ProgressReset),
+                ),
         )
-        .add_system(
-            text_input_system
-                .label(SystemLabels::TextInput)
-                .before(SystemLabels::TextInputBlinkCursor),
+        .init_resource::<WidgetsActive>()
+        .add_event::<ResetProgress>()
+        .add_event::<ProgressComplete>();
+    }
+}
+
+/// Registers the systems, resources and events of [`TextInputBundle`], [`SegmentedInputBundle`],
+/// forms, validation and the typewriter effect
+pub struct TextInputPlugin;
+
+impl Plugin for TextInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::new()
+                .label(SlimyWidgetsSet::TextInput)
+                .with_run_criteria(widgets_active_run_criteria)
+                .with_system(
+                    text_input_unfocus_system
+                        .label(SystemLabels::TextInputUnfocus)
+                        .before(SystemLabels::TextInputFocusOnClick),
+                )
+                .with_system(
+                    text_input_focus_on_click_system.label(SystemLabels::TextInputFocusOnClick),
+                )
+                .with_system(
+                    text_input_move_cursor_system
+                        .label(SystemLabels::TextInputMoveCursor)
+                        .after(SystemLabels::TextInputFontDecrease),
+                )
+                .with_system(
+                    text_input_blink_cursor_system.label(SystemLabels::TextInputBlinkCursor),
+                )
+                .with_system(text_input_create_system.label(SystemLabels::TextInputCreate))
+                .with_system(
+                    text_input_update_system
+                        .label(SystemLabels::TextInputUpdate)
+                        .after(SystemLabels::TextInputCreate),
+                )
+                .with_system(
+                    text_input_mode_system
+                        .label(SystemLabels::TextInputMode)
+                        .before(SystemLabels::TextInput),
+                )
+                .with_system(
+                    text_input_system
+                        .label(SystemLabels::TextInput)
+                        .label(SystemLabels::TextInputSubmit)
+                        .before(SystemLabels::TextInputBlinkCursor),
+                )
+                .with_system(
+                    text_input_font_decrease_system
+                        .label(SystemLabels::TextInputFontDecrease)
+                        .before(SystemLabels::TextInputUpdate)
+                        .after(SystemLabels::TextInput),
+                )
+                .with_system(
+                    text_input_padding_system
+                        .label(SystemLabels::TextInputPadding)
+                        .after(SystemLabels::TextInputCreate),
+                )
+                .with_system(
+                    text_input_file_drop_system
+                        .label(SystemLabels::TextInputFileDrop)
+                        .after(SystemLabels::TextInput)
+                        .before(SystemLabels::TextInputFontDecrease),
+                )
+                .with_system(
+                    text_input_focus_style_system
+                        .label(SystemLabels::TextInputFocusStyle)
+                        .after(SystemLabels::TextInputFocusOnClick)
+                        .after(SystemLabels::TextInputClickPadding),
+                )
+                .with_system(
+                    text_input_reset_system
+                        .label(SystemLabels::TextInputReset)
+                        .after(SystemLabels::TextInput)
+                        .before(SystemLabels::TextInputMoveCursor)
+                        .before(SystemLabels::TextInputUpdate),
+                )
+                .with_system(
+                    segmented_input_create_system.label(SystemLabels::SegmentedInputCreate),
+                )
+                .with_system(
+                    segmented_input_update_system
+                        .label(SystemLabels::SegmentedInputUpdate)
+                        .after(SystemLabels::SegmentedInputCreate)
+                        .after(SystemLabels::TextInput),
+                )
+                .with_system(
+                    text_input_auto_width_system
+                        .label(SystemLabels::TextInputAutoWidth)
+                        .after(SystemLabels::TextInput),
+                )
+                .with_system(
+                    text_input_adornment_create_system
+                        .label(SystemLabels::TextInputAdornmentCreate),
+                )
+                .with_system(
+                    text_input_adornment_system
+                        .label(SystemLabels::TextInputAdornment)
+                        .after(SystemLabels::TextInputAdornmentCreate)
+                        .after(SystemLabels::TextInputPadding)
+                        .after(SystemLabels::TextInput)
+                        .before(SystemLabels::TextInputMoveCursor),
+                )
+                .with_system(
+                    form_submit_system
+                        .label(SystemLabels::FormSubmit)
+                        .after(SystemLabels::TextInput)
+                        .after(SystemLabels::TextInputValidate),
+                )
+                .with_system(
+                    typewriter_reveal_system
+                        .label(SystemLabels::TypewriterReveal)
+                        .after(SystemLabels::TextInput)
+                        .before(SystemLabels::TextInputUpdate)
+                        .before(SystemLabels::TextInputMoveCursor),
+                )
+                .with_system(
+                    text_input_click_padding_create_system
+                        .label(SystemLabels::TextInputClickPaddingCreate),
+                )
+                .with_system(
+                    text_input_click_padding_system
+                        .label(SystemLabels::TextInputClickPadding)
+                        .after(SystemLabels::TextInputFocusOnClick),
+                )
+                .with_system(
+                    text_input_validation_reset_system
+                        .label(SystemLabels::TextInputValidationReset)
+                        .after(SystemLabels::TextInput)
+                        .after(SystemLabels::TextInputReset)
+                        .after(SystemLabels::TypewriterReveal),
+                )
+                .with_system(
+                    text_input_placeholder_focus_system
+                        .label(SystemLabels::TextInputPlaceholderFocus)
+                        .after(SystemLabels::TextInputUpdate)
+                        .after(SystemLabels::TextInputFocusOnClick)
+                        .after(SystemLabels::TextInputClickPadding),
+                )
+                .with_system(
+                    text_input_selection_highlight_system
+                        .label(SystemLabels::TextInputSelectionHighlight)
+                        .after(SystemLabels::TextInputMoveCursor),
+                )
+                .with_system(
+                    text_input_changed_event_system
+                        .label(SystemLabels::TextInputChangedEvent)
+                        .after(SystemLabels::TextInput)
+                        .after(SystemLabels::TextInputFileDrop)
+                        .after(SystemLabels::TextInputReset)
+                        .after(SystemLabels::TypewriterReveal),
+                )
+                .with_system(
+                    text_input_status_system
+                        .label(SystemLabels::TextInputStatus)
+                        .after(SystemLabels::TextInputFocusOnClick)
+                        .after(SystemLabels::TextInputClickPadding)
+                        .before(SystemLabels::TextInputFocusStyle),
+                )
+                .with_system(
+                    text_input_drag_system
+                        .label(SystemLabels::TextInputDrag)
+                        .after(SystemLabels::TextInputFocusOnClick)
+                        .before(SystemLabels::TextInputMoveCursor)
+                        .before(SystemLabels::TextInputSelectionHighlight),
+                )
+                .with_system(
+                    text_input_scrollbar_create_system
+                        .label(SystemLabels::TextInputScrollbarCreate),
+                )
+                .with_system(
+                    text_input_scroll_system
+                        .label(SystemLabels::TextInputScroll)
+                        .after(SystemLabels::TextInput)
+                        .after(SystemLabels::TextInputPadding)
+                        .after(SystemLabels::TextInputFontDecrease)
+                        .before(SystemLabels::TextInputMoveCursor),
+                )
+                .with_system(
+                    text_input_tab_focus_system
+                        .label(SystemLabels::TextInputTabFocus)
+                        .after(SystemLabels::TextInput)
+                        .before(SystemLabels::TextInputMoveCursor),
+                )
+                .with_system(
+                    text_input_auto_grow_system
+                        .label(SystemLabels::TextInputAutoGrow)
+                        .after(SystemLabels::TextInput),
+                )
+                .with_system(
+                    text_input_counter_create_system.label(SystemLabels::TextInputCounterCreate),
+                )
+                .with_system(
+                    text_input_counter_system
+                        .label(SystemLabels::TextInputCounter)
+                        .after(SystemLabels::TextInputCounterCreate)
+                        .after(SystemLabels::TextInput)
+                        .after(SystemLabels::TextInputReset),
+                )
+                .with_system(
+                    text_input_cursor_glide_system
+                        .label(SystemLabels::TextInputCursorGlide)
+                        .after(SystemLabels::TextInputMoveCursor),
+                )
+                .with_system(
+                    text_input_focus_event_system
+                        .label(SystemLabels::TextInputFocusEvent)
+                        .after(SystemLabels::TextInput)
+                        .after(SystemLabels::TextInputUnfocus)
+                        .after(SystemLabels::TextInputFocusOnClick)
+                        .after(SystemLabels::TextInputClickPadding)
+                        .after(SystemLabels::TextInputTabFocus)
+                        .after(SystemLabels::TextInputStatus)
+                        .after(SystemLabels::TextInputReset),
+                )
+                .with_system(
+                    glyph_calculator_cache_system.label(SystemLabels::GlyphCalculatorCache),
+                )
+                .with_system(
+                    text_input_clamp_cursor_system
+                        .label(SystemLabels::TextInputClampCursor)
+                        .before(SystemLabels::TextInput)
+                        .before(SystemLabels::TextInputMoveCursor)
+                        .before(SystemLabels::TextInputSelectionHighlight),
+                ),
         )
-        .add_system(
-            text_input_font_decrease_system
-                .label(SystemLabels::TextInputFontDecrease)
-                .before(SystemLabels::TextInputUpdate)
-                .after(SystemLabels::TextInput),
-        );
+        .init_resource::<WidgetsActive>()
+        .init_resource::<TextInputKeybinds>()
+        .init_resource::<TextInputClipboard>()
+        .init_resource::<MultiClickInterval>()
+        .init_resource::<KeyRepeatConfig>()
+        .init_resource::<GlyphCalculatorCache>()
+        .add_event::<TextInputTruncated>()
+        .add_event::<ResetTextInput>()
+        .add_event::<SubmitForm>()
+        .add_event::<FormSubmitted>()
+        .add_event::<TypewriterFinished>()
+        .add_event::<TextInputChanged>()
+        .add_event::<TextInputSubmit>()
+        .add_event::<TextInputRejected>()
+        .add_event::<TextInputFocused>()
+        .add_event::<TextInputBlurred>();
     }
 }
 
+/// A resource that pauses the systems of [`ProgressBarPlugin`] and [`TextInputPlugin`] when false,
+/// e.g. so that typing while a pause menu is open doesn't leak into a text input.
+/// Defaults to true
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_slimy_widgets::WidgetsActive;
+///
+/// fn pause_menu_system(keys: Res<Input<KeyCode>>, mut active: ResMut<WidgetsActive>) {
+///     if keys.just_pressed(KeyCode::Escape) {
+///         active.0 = !active.0;
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WidgetsActive(pub bool);
+
+impl Default for WidgetsActive {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+fn widgets_active_run_criteria(active: Res<WidgetsActive>) -> ShouldRun {
+    if active.0 {
+        ShouldRun::Yes
+    } else {
+        ShouldRun::No
+    }
+}
+
+/// Labels that all systems of a widget have, for ordering your systems before or after
+/// the whole widget, e.g. `.after(SlimyWidgetsSet::TextInput)`.
+/// See [`SystemLabels`] for the individual systems
+#[derive(SystemLabel, Clone, Hash, PartialEq, Eq, Debug)]
+pub enum SlimyWidgetsSet {
+    /// Systems added by [`ProgressBarPlugin`] and [`ProgressBindingAppExt`](crate::progress_bar::ProgressBindingAppExt)
+    ProgressBar,
+    /// Systems added by [`TextInputPlugin`] and [`TextInputValidationAppExt`](widgets::validation::TextInputValidationAppExt)
+    TextInput,
+}
+
 /// [`Labels`](bevy::ecs::schedule::SystemLabel) in [`bevy`] are used for system ordering.
 /// See [System Order of Execution][cheatbook_system_order] on unofficial bevy cheatbook for details.
 ///
@@ -66,13 +434,17 @@ impl Plugin for SlimyWidgetsPlugin {
 pub enum SystemLabels {
     /// [`ProgressBarBundle`]'s [`ProgressBarSizeAnimation`](crate::progress_bar::ProgressBarSizeAnimation) animation system
     ProgressBarSizeAnimation,
+    /// Decide whether [`ProgressBarBundle`]s with [`ProgressBarThrottle`](crate::progress_bar::ProgressBarThrottle) refresh this frame
+    ProgressBarThrottle,
+    /// Move [`Progress`](crate::progress_bar::Progress) towards [`ProgressTarget`](crate::progress_bar::ProgressTarget)
+    ProgressRate,
     /// Focus [`TextInputBundle`] when clicked on it
     TextInputFocusOnClick,
     /// Move [`TextInputBundle`]'s cursor
     TextInputMoveCursor,
     /// [`TextInputBundle`]'s cursor blinking
     TextInputBlinkCursor,
-    /// Unfocus all [`TextInputBundle`]s on click
+    /// Unfocus all [`TextInputBundle`]s on click, except the clicked one
     TextInputUnfocus,
     /// Create placeholder and inner text value when [`TextInputBundle`] is creatd
     TextInputCreate,
@@ -82,4 +454,90 @@ pub enum SystemLabels {
     TextInput,
     /// Decrease [`TextInputBundle`]'s font size based on text length and [`TextInputTargetSize`](widgets::text_input::TextInputTargetSize)
     TextInputFontDecrease,
+    /// Apply [`FocusStyle`](widgets::text_input::FocusStyle) when [`TextInputBundle`] gains or loses focus
+    TextInputFocusStyle,
+    /// Inset [`TextInputBundle`]'s text by its padding and border when its [`Style`] changes
+    TextInputPadding,
+    /// Insert paths of files dropped onto the window into the focused [`TextInputBundle`]
+    TextInputFileDrop,
+    /// Handle [`ResetTextInput`](widgets::text_input::ResetTextInput) events
+    TextInputReset,
+    /// Spawn cells when [`SegmentedInputBundle`] is created
+    SegmentedInputCreate,
+    /// Update [`SegmentedInputBundle`]'s cells
+    SegmentedInputUpdate,
+    /// Resize [`TextInputBundle`]s with [`AutoWidth`](widgets::text_input::AutoWidth) to fit their text
+    TextInputAutoWidth,
+    /// Spawn prefix and suffix when [`InputAdornment`](widgets::text_input::InputAdornment) is added
+    TextInputAdornmentCreate,
+    /// Position [`InputAdornment`](widgets::text_input::InputAdornment)'s prefix and suffix around the value
+    TextInputAdornment,
+    /// Handle [`SubmitForm`](widgets::form::SubmitForm) events
+    FormSubmit,
+    /// Reveal [`TypewriterReveal`](widgets::typewriter::TypewriterReveal)'s text over time
+    TypewriterReveal,
+    /// Spawn the hit area when [`ClickPadding`](widgets::text_input::ClickPadding) is added
+    TextInputClickPaddingCreate,
+    /// Handle [`ResetProgress`](crate::progress_bar::ResetProgress) events
+    ProgressReset,
+    /// Reset [`ValidationState`](widgets::validation::ValidationState) when the value changes
+    TextInputValidationReset,
+    /// Validators added with [`TextInputValidationAppExt`](widgets::validation::TextInputValidationAppExt)
+    TextInputValidate,
+    /// Hide the placeholder of focused [`TextInputBundle`]s with [`PlaceholderHideOnFocus`](widgets::text_input::PlaceholderHideOnFocus)
+    TextInputPlaceholderFocus,
+    /// Highlight [`TextInputBundle`]'s selected text
+    TextInputSelectionHighlight,
+    /// Send [`TextInputChanged`](widgets::text_input::TextInputChanged) events
+    TextInputChangedEvent,
+    /// Send [`TextInputSubmit`](widgets::text_input::TextInputSubmit) events. The same system as [`SystemLabels::TextInput`]
+    TextInputSubmit,
+    /// Unfocus and recolor [`TextInputBundle`]s when their [`TextInputStatus`](widgets::text_input::TextInputStatus) changes
+    TextInputStatus,
+    /// Select text by dragging over a [`TextInputBundle`] with [`TextInputDrag`](widgets::text_input::TextInputDrag)
+    TextInputDrag,
+    /// Spawn the bar when [`TextInputScrollbar`](widgets::text_input::TextInputScrollbar) is added
+    TextInputScrollbarCreate,
+    /// Keep the cursor line of [`TextInputScroll`](widgets::text_input::TextInputScroll) inputs in view
+    TextInputScroll,
+    /// Move the focus between [`TextInputBundle`]s with [`TabIndex`](widgets::text_input::TabIndex) on Tab
+    TextInputTabFocus,
+    /// [`ProgressBarBundle`]'s [`ProgressBarColorAnimation`](crate::progress_bar::ProgressBarColorAnimation) animation system
+    ProgressBarColorAnimation,
+    /// Slide [`IndeterminateProgress`](crate::progress_bar::IndeterminateProgress)'s highlight across the bar
+    ProgressBarIndeterminate,
+    /// Spawn the text when [`ProgressBarLabel`](crate::progress_bar::ProgressBarLabel) is added
+    ProgressBarLabelCreate,
+    /// Update [`ProgressBarLabel`](crate::progress_bar::ProgressBarLabel)'s text when [`Progress`](crate::progress_bar::Progress) changes
+    ProgressBarLabel,
+    /// Send [`ProgressComplete`](crate::progress_bar::ProgressComplete) events
+    ProgressComplete,
+    /// Spawn the blocks when [`SegmentedProgress`](crate::progress_bar::SegmentedProgress) is added
+    ProgressBarSegmentsCreate,
+    /// Fill [`SegmentedProgress`](crate::progress_bar::SegmentedProgress)'s blocks when [`Progress`](crate::progress_bar::Progress) changes
+    ProgressBarSegments,
+    /// Spawn the fill when [`BufferedProgress`](crate::progress_bar::BufferedProgress) is added
+    ProgressBarBufferedCreate,
+    /// Spawn the slices when [`RadialProgressSegments`](crate::progress_bar::RadialProgressSegments) is added
+    RadialProgressCreate,
+    /// Light up [`RadialProgressSegments`](crate::progress_bar::RadialProgressSegments)'s slices when [`Progress`](crate::progress_bar::Progress) changes
+    RadialProgress,
+    /// Resize [`TextInputBundle`]s with [`TextInputAutoGrow`](widgets::text_input::TextInputAutoGrow) to fit their lines
+    TextInputAutoGrow,
+    /// Spawn the text when [`TextInputCounter`](widgets::text_input::TextInputCounter) is added
+    TextInputCounterCreate,
+    /// Update [`TextInputCounter`](widgets::text_input::TextInputCounter)'s text when the value changes
+    TextInputCounter,
+    /// Move [`TextCursor`](widgets::text_input::TextCursor)s with [`CursorMovementAnimation`](widgets::text_input::CursorMovementAnimation) to their new position
+    TextInputCursorGlide,
+    /// Send [`TextInputFocused`](widgets::text_input::TextInputFocused) and [`TextInputBlurred`](widgets::text_input::TextInputBlurred) events
+    TextInputFocusEvent,
+    /// Drop [`GlyphCalculatorCache`](widgets::text_input::GlyphCalculatorCache) entries whose fonts were loaded, changed or removed
+    GlyphCalculatorCache,
+    /// Move the cursor and the selection anchor back into [`TextInputValue`](widgets::text_input::TextInputValue)s that were shortened
+    TextInputClampCursor,
+    /// Remove the new line constrain from [`TextInputBundle`]s with [`InputMode::Multiline`](widgets::text_input::InputMode::Multiline)
+    TextInputMode,
+    /// Focus [`TextInputBundle`] when clicked on its [`ClickPadding`](widgets::text_input::ClickPadding). Runs after [`SystemLabels::TextInputFocusOnClick`]
+    TextInputClickPadding,
 }
@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+
+use crate::progress_bar::Progress;
+use crate::text_input::{TextInputFocus, TextInputValue};
+
+/// Helpers for finding widget entities without remembering which components identify them.
+/// Useful for debugging and global operations like "unfocus all" or "reset all progress".
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_slimy_widgets::{ProgressBarBundle, WidgetQueryExt};
+///
+/// let mut world = World::new();
+/// world.spawn().insert_bundle(ProgressBarBundle::default());
+///
+/// assert_eq!(world.all_progress_bars().len(), 1);
+/// assert!(world.all_text_inputs().is_empty());
+/// ```
+pub trait WidgetQueryExt {
+    /// All entities spawned with [`TextInputBundle`](crate::TextInputBundle)
+    fn all_text_inputs(&mut self) -> Vec<Entity>;
+    /// All entities spawned with [`ProgressBarBundle`](crate::ProgressBarBundle)
+    fn all_progress_bars(&mut self) -> Vec<Entity>;
+}
+
+impl WidgetQueryExt for World {
+    fn all_text_inputs(&mut self) -> Vec<Entity> {
+        self.query_filtered::<Entity, (With<TextInputValue>, With<TextInputFocus>)>()
+            .iter(self)
+            .collect()
+    }
+
+    fn all_progress_bars(&mut self) -> Vec<Entity> {
+        self.query_filtered::<Entity, With<Progress>>()
+            .iter(self)
+            .collect()
+    }
+}
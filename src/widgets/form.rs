@@ -0,0 +1,65 @@
+//! Groups several text inputs into a form, so their values can be read in one place on submit.
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+use crate::text_input::{TextInputConstrains, TextInputValue};
+use crate::validation::ValidationState;
+
+/// Links text input entities into one form. Put it on any entity, e.g. the form's root node
+#[derive(Component, Clone, Debug, Default)]
+pub struct FormGroup(pub Vec<Entity>);
+
+/// Send this event to collect the values of a [`FormGroup`] into a [`FormSubmitted`] event
+#[derive(Clone, Copy, Debug)]
+pub struct SubmitForm {
+    /// The entity with [`FormGroup`]
+    pub group: Entity,
+}
+
+/// Sent in response to [`SubmitForm`]
+#[derive(Clone, Debug)]
+pub struct FormSubmitted {
+    /// The entity with [`FormGroup`]
+    pub group: Entity,
+    /// Values of the text inputs in the group
+    pub values: HashMap<Entity, String>,
+    /// Whether every value satisfies its input's [`TextInputConstrains`] and none of the inputs
+    /// has [`ValidationState::Invalid`]. A value can break the constrains if it was set from code
+    pub valid: bool,
+}
+
+pub fn form_submit_system(
+    mut events: EventReader<SubmitForm>,
+    mut submitted: EventWriter<FormSubmitted>,
+    groups: Query<&FormGroup>,
+    inputs: Query<(
+        &TextInputValue,
+        Option<&TextInputConstrains>,
+        Option<&ValidationState>,
+    )>,
+) {
+    for event in events.iter() {
+        let group = match groups.get(event.group) {
+            Ok(group) => group,
+            Err(_) => continue,
+        };
+        let mut values = HashMap::default();
+        let mut valid = true;
+        for &member in &group.0 {
+            match inputs.get(member) {
+                Ok((value, constrains, state)) => {
+                    valid &= constrains.is_none_or(|constrains| constrains.test(value, value));
+                    valid &= !matches!(state, Some(ValidationState::Invalid(_)));
+                    values.insert(member, value.0.clone());
+                }
+                Err(_) => valid = false,
+            }
+        }
+        submitted.send(FormSubmitted {
+            group: event.group,
+            values,
+            valid,
+        });
+    }
+}
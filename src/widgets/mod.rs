@@ -1,2 +1,6 @@
+pub mod form;
 pub mod progress_bar;
+pub mod segmented_input;
 pub mod text_input;
+pub mod typewriter;
+pub mod validation;
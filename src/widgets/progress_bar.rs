@@ -1,7 +1,10 @@
 //! A progress bar widget. You may want to use this with [`bevy_loading`](https://github.com/IyesGames/bevy_loading).
 
 use bevy::prelude::*;
-use std::ops::{AddAssign, Deref};
+use bevy::text::{HorizontalAlign, VerticalAlign};
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Deref, Sub, SubAssign};
+use std::time::Duration;
 
 /// Progress struct for ProgressBar.
 /// ```
@@ -15,16 +18,28 @@ use std::ops::{AddAssign, Deref};
 /// let progress_bar_width = Val::Percent(*progress_bar);
 /// ```
 ///
-/// Note: values will be clamped between 0.0 and 100.0
-#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Reflect)]
+/// Note: values will be clamped between 0.0 and 100.0, unless overfill is allowed
+/// with [`Progress::with_overfill`]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
 #[reflect(Component)]
-pub struct Progress(f32);
+pub struct Progress {
+    value: f32,
+    min: f32,
+    done: f32,
+    max: f32,
+}
+
+impl Default for Progress {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
 
 impl Deref for Progress {
     type Target = f32;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.value
     }
 }
 
@@ -37,7 +52,65 @@ impl Progress {
     /// let progress_bar = Progress::new(100.0);
     /// ```
     pub fn new(value: f32) -> Self {
-        Progress(Self::clamp_value(value))
+        Self::with_overfill(value, 100.0)
+    }
+
+    /// Creates a new instance of [`Progress`] that can hold values above 100%, up to `max`.
+    /// Useful for bars that briefly overfill, e.g. an XP bar right before level-up.
+    /// [`ProgressBarSizeAnimation`] still displays at most 100%
+    /// ```
+    /// # use bevy_slimy_widgets::progress_bar::Progress;
+    ///
+    /// let mut progress_bar = Progress::with_overfill(90.0, 150.0);
+    /// progress_bar += 30.0;
+    /// assert!(progress_bar.is_done());
+    /// assert_eq!(*progress_bar, 120.0);
+    /// assert_eq!(progress_bar.overfill(), 20.0);
+    /// ```
+    pub fn with_overfill(value: f32, max: f32) -> Self {
+        let mut progress = Progress {
+            value: 0.0,
+            min: 0.0,
+            done: 100.0,
+            max: max.max(100.0),
+        };
+        progress.set(value);
+        progress
+    }
+
+    /// Creates a new instance of [`Progress`] that holds values from `range.min` to `range.max`
+    /// instead of percents, e.g. bytes downloaded. Insert the same [`ProgressRange`] into the bar
+    /// so that the visuals display the right fraction
+    /// ```
+    /// # use bevy_slimy_widgets::progress_bar::{Progress, ProgressRange};
+    ///
+    /// let range = ProgressRange { min: 0.0, max: 2048.0 };
+    /// let mut progress_bar = Progress::with_range(512.0, range);
+    /// assert_eq!(progress_bar.fraction(&range), 0.25);
+    ///
+    /// progress_bar += 2000.0;
+    /// assert_eq!(*progress_bar, 2048.0);
+    /// assert!(progress_bar.is_done());
+    /// ```
+    pub fn with_range(value: f32, range: ProgressRange) -> Self {
+        let mut progress = Progress {
+            value: 0.0,
+            min: range.min,
+            done: range.max,
+            max: range.max.max(range.min),
+        };
+        progress.set(value);
+        progress
+    }
+
+    /// Creates a new instance of [`Progress`] from a fraction, where 1.0 is 100%
+    /// ```
+    /// # use bevy_slimy_widgets::progress_bar::Progress;
+    ///
+    /// assert_eq!(*Progress::from_ratio(0.25), 25.0);
+    /// ```
+    pub fn from_ratio(ratio: f32) -> Self {
+        Self::new(ratio * 100.0)
     }
 
     /// Creates a new instance of [`Progress`] with 0% done
@@ -53,10 +126,11 @@ impl Progress {
 
     /// Sets the progress value
     pub fn set(&mut self, value: f32) {
-        self.0 = Self::clamp_value(value)
+        self.value = value.clamp(self.min, self.max)
     }
 
-    /// Check if this [`Progress`] has reached 100%
+    /// Check if this [`Progress`] has reached 100%, or the end of the range
+    /// if created with [`Progress::with_range`]
     /// ```
     /// # use bevy_slimy_widgets::progress_bar::Progress;
     ///
@@ -67,11 +141,120 @@ impl Progress {
     /// assert!(progress_bar.is_done());
     /// ```
     pub fn is_done(&self) -> bool {
-        (self.0 - 100.0).abs() < f32::EPSILON
+        self.value >= self.done - f32::EPSILON
     }
 
-    fn clamp_value(value: f32) -> f32 {
-        value.clamp(0.0, 100.0)
+    /// How far the value is above 100%. Always 0.0 unless created with [`Progress::with_overfill`]
+    pub fn overfill(&self) -> f32 {
+        (self.value - self.done).max(0.0)
+    }
+
+    /// Adds `delta` like `+=` and returns whether this call made the progress
+    /// [done](Progress::is_done). Returns false if it was already done
+    /// ```
+    /// # use bevy_slimy_widgets::progress_bar::Progress;
+    ///
+    /// let mut progress_bar = Progress::new(80.0);
+    /// assert!(!progress_bar.increment(10.0));
+    /// assert!(progress_bar.increment(10.0));
+    ///
+    /// // already at 100%
+    /// assert!(!progress_bar.increment(10.0));
+    /// assert_eq!(*progress_bar, 100.0);
+    /// ```
+    pub fn increment(&mut self, delta: f32) -> bool {
+        let was_done = self.is_done();
+        *self += delta;
+        !was_done && self.is_done()
+    }
+
+    /// How much is left until the progress is [done](Progress::is_done). 0.0 if it's done
+    /// ```
+    /// # use bevy_slimy_widgets::progress_bar::Progress;
+    ///
+    /// assert_eq!(Progress::new(70.0).remaining(), 30.0);
+    /// assert_eq!(Progress::with_overfill(120.0, 150.0).remaining(), 0.0);
+    /// ```
+    pub fn remaining(&self) -> f32 {
+        (self.done - self.value).max(0.0)
+    }
+
+    /// How much of `range` is done, from 0.0 to 1.0
+    /// ```
+    /// # use bevy_slimy_widgets::progress_bar::{Progress, ProgressRange};
+    ///
+    /// assert_eq!(Progress::new(40.0).fraction(&ProgressRange::default()), 0.4);
+    /// ```
+    pub fn fraction(&self, range: &ProgressRange) -> f32 {
+        let width = range.max - range.min;
+        if width.abs() < f32::EPSILON {
+            return if self.value < range.max { 0.0 } else { 1.0 };
+        }
+        ((self.value - range.min) / width).clamp(0.0, 1.0)
+    }
+}
+
+/// The values of [`Progress`] that an empty and a full bar display. Without this component,
+/// bars display percents, from 0.0 to 100.0. Use it with [`Progress::with_range`]
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct ProgressRange {
+    /// The value of an empty bar
+    pub min: f32,
+    /// The value of a full bar
+    pub max: f32,
+}
+
+impl Default for ProgressRange {
+    fn default() -> Self {
+        Self {
+            min: 0.0,
+            max: 100.0,
+        }
+    }
+}
+
+/// Treats the value as a percentage, same as [`Progress::new`]
+/// ```
+/// # use bevy_slimy_widgets::progress_bar::Progress;
+///
+/// let progress: Progress = 30.0.into();
+/// assert_eq!(*progress, 30.0);
+///
+/// let progress: Progress = 130.0.into();
+/// assert_eq!(*progress, 100.0);
+/// ```
+impl From<f32> for Progress {
+    fn from(value: f32) -> Self {
+        Self::new(value)
+    }
+}
+
+/// Converts a `(current, total)` pair. A zero `total` gives an empty [`Progress`]
+/// ```
+/// # use bevy_slimy_widgets::progress_bar::Progress;
+///
+/// let progress: Progress = (3.0, 4.0).into();
+/// assert_eq!(*progress, 75.0);
+/// ```
+impl From<(f32, f32)> for Progress {
+    fn from((current, total): (f32, f32)) -> Self {
+        if total.abs() < f32::EPSILON {
+            Self::empty()
+        } else {
+            Self::from_ratio(current / total)
+        }
+    }
+}
+
+/// ```
+/// # use bevy_slimy_widgets::progress_bar::Progress;
+///
+/// let value: f32 = Progress::new(30.0).into();
+/// assert_eq!(value, 30.0);
+/// ```
+impl From<Progress> for f32 {
+    fn from(progress: Progress) -> Self {
+        progress.value
     }
 }
 
@@ -81,41 +264,1297 @@ impl AddAssign<f32> for Progress {
     }
 }
 
-/// Progress bar resize animation
-#[derive(Component, Debug, Clone, Copy, PartialEq)]
+/// ```
+/// # use bevy_slimy_widgets::progress_bar::Progress;
+///
+/// let mut progress = Progress::new(30.0);
+/// progress -= 10.0;
+/// assert_eq!(*progress, 20.0);
+///
+/// progress -= 50.0;
+/// assert_eq!(*progress, 0.0);
+/// ```
+impl SubAssign<f32> for Progress {
+    fn sub_assign(&mut self, rhs: f32) {
+        self.set(**self - rhs)
+    }
+}
+
+/// Keeps the bounds of `self`, e.g. [overfill](Progress::with_overfill)
+/// ```
+/// # use bevy_slimy_widgets::progress_bar::Progress;
+///
+/// let progress = Progress::new(30.0);
+/// assert_eq!(*(progress + 20.0), 50.0);
+/// assert_eq!(*(progress + 90.0), 100.0);
+/// ```
+impl Add<f32> for Progress {
+    type Output = Progress;
+
+    fn add(mut self, rhs: f32) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+/// Keeps the bounds of `self`, e.g. [overfill](Progress::with_overfill)
+/// ```
+/// # use bevy_slimy_widgets::progress_bar::Progress;
+///
+/// let progress = Progress::new(30.0);
+/// assert_eq!(*(progress - 20.0), 10.0);
+/// assert_eq!(*(progress - 40.0), 0.0);
+/// ```
+impl Sub<f32> for Progress {
+    type Output = Progress;
+
+    fn sub(mut self, rhs: f32) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+/// Progress bar resize animation. With [`ProgressRange`], the fraction of the range is displayed
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default)]
 pub enum ProgressBarSizeAnimation {
-    /// The width of a node will be changed to [`Val::Percent`]\(*progress)
-    /// when the [`Progress`] changes
+    /// The width of a node will be changed to `*progress` percent
+    /// of the parent's content box when the [`Progress`] changes
+    #[default]
     Width,
-    /// The height of a node will be changed to [`Val::Percent`]\(*progress)
-    /// when the [`Progress`] changes
+    /// The height of a node will be changed to `*progress` percent
+    /// of the parent's content box when the [`Progress`] changes
     Height,
-    /// Both the width and the height of a node will be changed to [`Val::Percent`]\(*progress)
-    /// when the [`Progress`] changes
+    /// Both the width and the height of a node will be changed to `*progress` percent
+    /// of the parent's content box when the [`Progress`] changes
     Both,
 }
 
-impl Default for ProgressBarSizeAnimation {
-    fn default() -> Self {
-        ProgressBarSizeAnimation::Width
+/// The value [`Progress`] moves towards at [`ProgressRate`]
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+pub struct ProgressTarget(pub f32);
+
+/// How fast [`Progress`] moves towards [`ProgressTarget`], in percents per second.
+/// Unlike visual animations, this changes the [`Progress`] value itself
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct ProgressRate(pub f32);
+
+/// Moves [`Progress`] towards [`ProgressTarget`] at [`ProgressRate`]
+pub fn progress_rate_system(
+    time: Res<Time>,
+    mut query: Query<(&mut Progress, &ProgressTarget, &ProgressRate)>,
+) {
+    for (mut progress, target, rate) in query.iter_mut() {
+        let difference = target.0.clamp(progress.min, progress.max) - **progress;
+        if difference.abs() < f32::EPSILON {
+            continue;
+        }
+        let step = rate.0 * time.delta_seconds();
+        let value = **progress + difference.clamp(-step, step);
+        progress.set(value);
     }
 }
 
-/// Updates progress bar [`Size`] if [`Progress`] has changed
+/// Limits how often a progress bar's visuals are refreshed. [`Progress`] itself stays precise,
+/// only the visual update systems wait until the interval has passed since the last refresh.
+///
+/// Useful when [`Progress`] changes many times per second, e.g. when hundreds of loading items
+/// are mapped to one bar.
+#[derive(Component, Debug, Clone)]
+pub struct ProgressBarThrottle {
+    timer: Timer,
+    pending: bool,
+    refresh: bool,
+}
+
+impl ProgressBarThrottle {
+    /// Refresh the visuals at most once per `interval`
+    pub fn new(interval: Duration) -> Self {
+        let mut timer = Timer::new(interval, false);
+        // the first change is displayed immediately
+        timer.set_elapsed(interval);
+        Self {
+            timer,
+            pending: false,
+            refresh: false,
+        }
+    }
+
+    /// Refresh the visuals at most `hz` times per second.
+    /// Zero, negative and NaN rates don't throttle at all
+    pub fn from_hz(hz: f32) -> Self {
+        if hz > 0.0 {
+            Self::new(Duration::from_secs_f32(1.0 / hz))
+        } else {
+            Self::new(Duration::ZERO)
+        }
+    }
+
+    /// Whether visual systems should refresh this frame
+    pub fn should_refresh(&self) -> bool {
+        self.refresh
+    }
+}
+
+/// Decides which throttled progress bars get their visuals refreshed this frame
+pub fn progress_bar_throttle_system(
+    time: Res<Time>,
+    mut query: Query<(ChangeTrackers<Progress>, &mut ProgressBarThrottle)>,
+) {
+    for (progress_tracker, mut throttle) in query.iter_mut() {
+        throttle.timer.tick(time.delta());
+        throttle.pending |= progress_tracker.is_changed();
+        throttle.refresh = throttle.pending && throttle.timer.finished();
+        if throttle.refresh {
+            throttle.pending = false;
+            throttle.timer.reset();
+        }
+    }
+}
+
+/// Updates progress bar [`Size`] if [`Progress`] or the size of its parent (the track) has changed.
+/// With [`ProgressBarSmoothing`], the size moves to the new value over time instead.
+///
+/// If the bar has a parent node, its size is set in pixels relative to the parent's content box,
+/// so 100% exactly fills the inside of a bordered or padded track. Only [`Val::Px`] padding,
+/// border and margin are subtracted. Bars without a parent use [`Val::Percent`]
 pub fn progress_bar_size_animation_system(
-    mut query: Query<(&Progress, &ProgressBarSizeAnimation, &mut Style), Changed<Progress>>,
+    time: Res<Time>,
+    mut query: Query<(
+        Entity,
+        ChangeTrackers<Progress>,
+        &Progress,
+        &ProgressBarSizeAnimation,
+        &mut Style,
+        Option<&ProgressBarThrottle>,
+        Option<&Parent>,
+        Option<ChangeTrackers<IndeterminateProgress>>,
+        Option<&mut ProgressBarSmoothing>,
+        Option<&ProgressRange>,
+        Option<(ChangeTrackers<BufferedProgress>, &BufferedProgress)>,
+    )>,
+    tracks: Query<
+        (ChangeTrackers<Node>, &Node, &Style),
+        (Without<Progress>, Without<BufferedProgressFill>),
+    >,
+    mut buffered_fills: Query<
+        (
+            ChangeTrackers<BufferedProgressFill>,
+            &BufferedProgressFill,
+            &mut Style,
+        ),
+        Without<Progress>,
+    >,
 ) {
-    for (progress, dimension, mut style) in query.iter_mut() {
+    for (
+        entity,
+        progress_tracker,
+        progress,
+        dimension,
+        mut style,
+        throttle,
+        parent,
+        indeterminate,
+        smoothing,
+        range,
+        buffered,
+    ) in query.iter_mut()
+    {
+        let range = range.copied().unwrap_or_default();
+        let track = parent.and_then(|parent| tracks.get(parent.get()).ok());
+        let track_changed = track.is_some_and(|(node_tracker, _, _)| node_tracker.is_changed());
+        let refresh = match throttle {
+            Some(throttle) => throttle.should_refresh(),
+            None => progress_tracker.is_changed(),
+        } || track_changed
+            || indeterminate.is_some_and(|tracker| tracker.is_added());
+        // a Progress change after IndeterminateProgress was added switches back to determinate,
+        // the component itself is removed at the end of the frame
+        let indeterminate = indeterminate
+            .is_some_and(|tracker| tracker.is_changed() || !progress_tracker.is_changed());
         let (resize_width, resize_height) = match dimension {
             ProgressBarSizeAnimation::Width => (true, false),
             ProgressBarSizeAnimation::Height => (false, true),
             ProgressBarSizeAnimation::Both => (true, true),
         };
+        if let (Some((buffered_tracker, buffered)), Some((_, node, track_style))) =
+            (buffered, track)
+        {
+            // clamped the same way as the progress
+            let mut buffered_progress = *progress;
+            buffered_progress.set(buffered.0);
+            let buffered_fill = buffered_progress.fraction(&range);
+            for (fill_tracker, _, mut fill_style) in buffered_fills
+                .iter_mut()
+                .filter(|(_, fill, _)| fill.bar == entity)
+            {
+                // the fill is spawned with commands, so it appears after the component is added
+                if buffered_tracker.is_changed() || track_changed || fill_tracker.is_added() {
+                    let left = edges_px(track_style.padding.left, track_style.border.left);
+                    let top = edges_px(track_style.padding.top, track_style.border.top);
+                    let bottom = edges_px(track_style.padding.bottom, track_style.border.bottom);
+                    // vertical bars fill from the bottom
+                    let (top, bottom) = if resize_width {
+                        (Val::Px(top), Val::Undefined)
+                    } else {
+                        (Val::Undefined, Val::Px(bottom))
+                    };
+                    fill_style.position = UiRect {
+                        left: Val::Px(left),
+                        right: Val::Undefined,
+                        top,
+                        bottom,
+                    };
+                    let content = track_content_size(node, track_style);
+                    fill_style.size = Size::new(
+                        Val::Px(if resize_width {
+                            buffered_fill * content.x
+                        } else {
+                            content.x
+                        }),
+                        Val::Px(if resize_height {
+                            buffered_fill * content.y
+                        } else {
+                            content.y
+                        }),
+                    );
+                }
+            }
+        }
+        // the indeterminate highlight slides across the track, so the bar itself is empty
+        let target = if indeterminate && track.is_some() {
+            0.0
+        } else {
+            progress.fraction(&range)
+        };
+        let fill = match smoothing {
+            Some(mut smoothing) => {
+                if refresh && (smoothing.to - target).abs() > f32::EPSILON {
+                    smoothing.retarget(target);
+                }
+                let animating = smoothing.elapsed < smoothing.duration;
+                if animating {
+                    smoothing.tick(time.delta());
+                } else if !refresh {
+                    continue;
+                }
+                smoothing.displayed
+            }
+            None if refresh => target,
+            None => continue,
+        };
         if resize_width {
-            style.size.width = Val::Percent(**progress);
+            style.size.width = match track {
+                Some((_, node, track_style)) => Val::Px(
+                    fill * (track_content_size(node, track_style).x
+                        - edges_px(style.margin.left, style.margin.right))
+                    .max(0.0),
+                ),
+                None => Val::Percent(fill * 100.0),
+            };
         }
         if resize_height {
-            style.size.height = Val::Percent(**progress);
+            style.size.height = match track {
+                Some((_, node, track_style)) => Val::Px(
+                    fill * (track_content_size(node, track_style).y
+                        - edges_px(style.margin.top, style.margin.bottom))
+                    .max(0.0),
+                ),
+                None => Val::Percent(fill * 100.0),
+            };
+        }
+    }
+}
+
+/// Makes [`ProgressBarSizeAnimation`] move to the new size over `duration` instead of jumping.
+/// [`Progress`] itself changes immediately, only the displayed size lags behind
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct ProgressBarSmoothing {
+    /// How long it takes to reach the new size
+    pub duration: Duration,
+    /// How the size moves over time
+    pub easing: Easing,
+    from: f32,
+    to: f32,
+    displayed: f32,
+    elapsed: Duration,
+}
+
+impl ProgressBarSmoothing {
+    /// Move to the new size over `duration`. The bar starts empty and grows to the initial progress
+    pub fn new(duration: Duration, easing: Easing) -> Self {
+        Self {
+            duration,
+            easing,
+            from: 0.0,
+            to: 0.0,
+            displayed: 0.0,
+            elapsed: duration,
+        }
+    }
+
+    /// The displayed fraction of the bar, from 0.0 to 1.0
+    pub fn displayed(&self) -> f32 {
+        self.displayed
+    }
+
+    fn retarget(&mut self, to: f32) {
+        self.from = self.displayed;
+        self.to = to;
+        self.elapsed = Duration::ZERO;
+    }
+
+    /// Jumps to `to` without animating
+    fn snap(&mut self, to: f32) {
+        self.from = to;
+        self.to = to;
+        self.displayed = to;
+        self.elapsed = self.duration;
+    }
+
+    fn tick(&mut self, delta: Duration) {
+        self.elapsed = (self.elapsed + delta).min(self.duration);
+        let t = if self.duration.is_zero() {
+            1.0
+        } else {
+            self.elapsed.as_secs_f32() / self.duration.as_secs_f32()
+        };
+        self.displayed = self.from + (self.to - self.from) * self.easing.apply(t);
+    }
+}
+
+/// How [`ProgressBarSmoothing`] moves over time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Easing {
+    /// Constant speed
+    Linear,
+    /// Starts and ends slowly
+    #[default]
+    EaseInOut,
+    /// Starts fast and ends slowly
+    EaseOut,
+}
+
+impl Easing {
+    /// Maps the elapsed fraction of the animation to the fraction of the distance covered
+    /// ```
+    /// # use bevy_slimy_widgets::progress_bar::Easing;
+    ///
+    /// assert_eq!(Easing::Linear.apply(0.25), 0.25);
+    /// assert!(Easing::EaseInOut.apply(0.25) < 0.25);
+    /// assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+    /// assert!(Easing::EaseOut.apply(0.25) > 0.25);
+    /// assert_eq!(Easing::EaseOut.apply(1.0), 1.0);
+    /// ```
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+            Easing::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+        }
+    }
+}
+
+/// Size of the track inside its [`Val::Px`] padding and border
+fn track_content_size(node: &Node, track_style: &Style) -> Vec2 {
+    Vec2::new(
+        node.size.x
+            - edges_px(track_style.padding.left, track_style.padding.right)
+            - edges_px(track_style.border.left, track_style.border.right),
+        node.size.y
+            - edges_px(track_style.padding.top, track_style.padding.bottom)
+            - edges_px(track_style.border.top, track_style.border.bottom),
+    )
+}
+
+fn edges_px(start: Val, end: Val) -> f32 {
+    [start, end]
+        .into_iter()
+        .map(|val| match val {
+            Val::Px(px) => px,
+            _ => 0.0,
+        })
+        .sum()
+}
+
+/// Progress bar color animation, from `from` at 0% to `to` at 100%.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_slimy_widgets::progress_bar::{ColorInterpolation, ProgressBarColorAnimation};
+///
+/// // red to green, through yellow instead of brown
+/// let animation = ProgressBarColorAnimation {
+///     from: Color::RED,
+///     to: Color::GREEN,
+///     interpolation: ColorInterpolation::Hsl,
+/// };
+/// ```
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct ProgressBarColorAnimation {
+    /// The color of an empty bar
+    pub from: Color,
+    /// The color of a full bar
+    pub to: Color,
+    /// How the colors in between are mixed
+    pub interpolation: ColorInterpolation,
+}
+
+/// How [`ProgressBarColorAnimation`] mixes its colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorInterpolation {
+    /// Mix red, green and blue separately. The midpoint of very different colors may look muddy
+    #[default]
+    Rgb,
+    /// Go around the hue wheel the shorter way, keeping the midpoint saturated
+    Hsl,
+}
+
+/// Updates progress bar [`UiColor`] if [`Progress`] has changed
+pub fn progress_bar_color_animation_system(
+    mut query: Query<(
+        ChangeTrackers<Progress>,
+        ChangeTrackers<ProgressBarColorAnimation>,
+        &Progress,
+        &ProgressBarColorAnimation,
+        &mut UiColor,
+        Option<&ProgressBarThrottle>,
+        Option<&ProgressRange>,
+    )>,
+) {
+    for (progress_tracker, animation_tracker, progress, animation, mut color, throttle, range) in
+        query.iter_mut()
+    {
+        let refresh = match throttle {
+            Some(throttle) => throttle.should_refresh(),
+            None => progress_tracker.is_changed(),
+        } || animation_tracker.is_changed();
+        if !refresh {
+            continue;
+        }
+        let t = progress.fraction(&range.copied().unwrap_or_default());
+        color.0 = match animation.interpolation {
+            ColorInterpolation::Rgb => {
+                let [r1, g1, b1, a1] = animation.from.as_rgba_f32();
+                let [r2, g2, b2, a2] = animation.to.as_rgba_f32();
+                Color::rgba(
+                    lerp(r1, r2, t),
+                    lerp(g1, g2, t),
+                    lerp(b1, b2, t),
+                    lerp(a1, a2, t),
+                )
+            }
+            ColorInterpolation::Hsl => {
+                let [h1, s1, l1, a1] = animation.from.as_hsla_f32();
+                let [h2, s2, l2, a2] = animation.to.as_hsla_f32();
+                // take the shorter way around the hue wheel
+                let hue_difference = (h2 - h1 + 540.0) % 360.0 - 180.0;
+                Color::hsla(
+                    (h1 + hue_difference * t).rem_euclid(360.0),
+                    lerp(s1, s2, t),
+                    lerp(l1, l2, t),
+                    lerp(a1, a2, t),
+                )
+            }
+        };
+    }
+}
+
+fn lerp(from: f32, to: f32, t: f32) -> f32 {
+    from + (to - from) * t
+}
+
+/// Shows that something is in progress when the amount of work isn't known.
+/// A highlight, a quarter of the bar wide, slides back and forth across the bar's parent (the track),
+/// or across the bar itself if it has no parent. It's colored like the bar, and the bar is emptied.
+///
+/// Changing [`Progress`], e.g. with [`Progress::set`], removes this component
+/// and switches back to the determinate display
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct IndeterminateProgress {
+    /// How many times per second the highlight crosses the bar
+    pub speed: f32,
+}
+
+impl Default for IndeterminateProgress {
+    fn default() -> Self {
+        Self { speed: 1.0 }
+    }
+}
+
+/// The node spawned for [`IndeterminateProgress`]
+#[derive(Component, Debug, Clone, Copy)]
+pub struct IndeterminateHighlight {
+    bar: Entity,
+    phase: f32,
+}
+
+/// Width of [`IndeterminateHighlight`], in percents of the track
+const INDETERMINATE_HIGHLIGHT_WIDTH: f32 = 25.0;
+
+pub fn progress_bar_indeterminate_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    added: Query<(Entity, Option<&Parent>, &UiColor), Added<IndeterminateProgress>>,
+    bars: Query<(
+        Entity,
+        &IndeterminateProgress,
+        ChangeTrackers<Progress>,
+        ChangeTrackers<IndeterminateProgress>,
+    )>,
+    mut highlights: Query<(Entity, &mut IndeterminateHighlight, &mut Style)>,
+) {
+    for (bar, parent, color) in added.iter() {
+        let container = parent.map_or(bar, |parent| parent.get());
+        commands.entity(container).with_children(|parent| {
+            parent
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        position: UiRect {
+                            left: Val::Percent(0.0),
+                            top: Val::Px(0.0),
+                            bottom: Val::Px(0.0),
+                            ..Default::default()
+                        },
+                        size: Size::new(
+                            Val::Percent(INDETERMINATE_HIGHLIGHT_WIDTH),
+                            Val::Undefined,
+                        ),
+                        ..Default::default()
+                    },
+                    color: *color,
+                    ..Default::default()
+                })
+                .insert(IndeterminateHighlight { bar, phase: 0.0 });
+        });
+    }
+
+    for (bar, _, progress_tracker, indeterminate_tracker) in bars.iter() {
+        if progress_tracker.is_changed() && !indeterminate_tracker.is_changed() {
+            commands.entity(bar).remove::<IndeterminateProgress>();
         }
     }
+
+    for (entity, mut highlight, mut style) in highlights.iter_mut() {
+        let speed = match bars.get(highlight.bar) {
+            Ok((_, indeterminate, ..)) => indeterminate.speed,
+            Err(_) => {
+                // the bar became determinate or was despawned
+                commands.entity(entity).despawn_recursive();
+                continue;
+            }
+        };
+        // one phase is there and back again, so twice the crossings per second
+        highlight.phase = (highlight.phase + time.delta_seconds() * speed / 2.0).fract();
+        let there_and_back = 1.0 - (2.0 * highlight.phase - 1.0).abs();
+        style.position.left =
+            Val::Percent(there_and_back * (100.0 - INDETERMINATE_HIGHLIGHT_WIDTH));
+    }
+}
+
+/// A text child of a progress bar that shows its [`Progress`], e.g. "42%".
+/// It's centered within the bar
+#[derive(Component, Debug, Clone)]
+pub struct ProgressBarLabel {
+    /// Style of the text
+    pub style: TextStyle,
+    /// Turns `*progress` into the label text. Default is the percent rounded to an integer
+    pub format: fn(f32) -> String,
+}
+
+impl Default for ProgressBarLabel {
+    fn default() -> Self {
+        Self {
+            style: Default::default(),
+            format: format_percent,
+        }
+    }
+}
+
+fn format_percent(progress: f32) -> String {
+    format!("{}%", progress.round())
+}
+
+/// The text node spawned for [`ProgressBarLabel`]
+#[derive(Component)]
+pub struct ProgressBarLabelText;
+
+pub fn progress_bar_label_create_system(
+    mut commands: Commands,
+    query: Query<(Entity, &Progress, &ProgressBarLabel), Added<ProgressBarLabel>>,
+) {
+    for (entity, progress, label) in query.iter() {
+        commands.entity(entity).with_children(|parent| {
+            parent
+                .spawn_bundle(TextBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        position: UiRect::all(Val::Px(0.0)),
+                        ..Default::default()
+                    },
+                    text: Text::from_section((label.format)(**progress), label.style.clone())
+                        .with_alignment(TextAlignment {
+                            vertical: VerticalAlign::Center,
+                            horizontal: HorizontalAlign::Center,
+                        }),
+                    ..Default::default()
+                })
+                .insert(ProgressBarLabelText);
+        });
+    }
+}
+
+pub fn progress_bar_label_system(
+    query: Query<(
+        Entity,
+        ChangeTrackers<Progress>,
+        ChangeTrackers<ProgressBarLabel>,
+        &Progress,
+        &ProgressBarLabel,
+        Option<&ProgressBarThrottle>,
+    )>,
+    mut labels: Query<(&Parent, &mut Text), With<ProgressBarLabelText>>,
+) {
+    for (entity, progress_tracker, label_tracker, progress, label, throttle) in query.iter() {
+        let refresh = match throttle {
+            Some(throttle) => throttle.should_refresh(),
+            None => progress_tracker.is_changed(),
+        } || label_tracker.is_changed();
+        if !refresh {
+            continue;
+        }
+        for (_, mut text) in labels
+            .iter_mut()
+            .filter(|(parent, _)| parent.get() == entity)
+        {
+            text.sections[0].value = (label.format)(**progress);
+            text.sections[0].style = label.style.clone();
+        }
+    }
+}
+
+/// Sent when a progress bar's [`Progress`] becomes [done](Progress::is_done).
+/// It's sent again only after the progress goes below done and reaches it again
+#[derive(Clone, Copy, Debug)]
+pub struct ProgressComplete {
+    /// The progress bar entity
+    pub entity: Entity,
+}
+
+/// Added to a progress bar when [`ProgressComplete`] is sent, and removed when the progress
+/// goes below done, so that the event isn't sent every time a done [`Progress`] changes
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct ProgressCompleteFired;
+
+pub fn progress_complete_system(
+    mut commands: Commands,
+    query: Query<(Entity, &Progress, Option<&ProgressCompleteFired>), Changed<Progress>>,
+    mut complete: EventWriter<ProgressComplete>,
+) {
+    for (entity, progress, fired) in query.iter() {
+        match (progress.is_done(), fired.is_some()) {
+            (true, false) => {
+                complete.send(ProgressComplete { entity });
+                commands.entity(entity).insert(ProgressCompleteFired);
+            }
+            (false, true) => {
+                commands.entity(entity).remove::<ProgressCompleteFired>();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Displays the progress as a row of blocks that fill one at a time, e.g. for installers.
+/// The blocks are spawned as children when this component is added, so changing
+/// `segments` later has no effect
+#[derive(Component, Debug, Clone, Copy)]
+pub struct SegmentedProgress {
+    /// Number of blocks
+    pub segments: usize,
+    /// Space between the blocks
+    pub gap: Val,
+    /// Whether a partially done block is filled partially or rounded
+    pub partial: PartialSegment,
+    /// Color of the done part of the blocks
+    pub filled_color: UiColor,
+    /// Color of the rest of the blocks
+    pub empty_color: UiColor,
+}
+
+impl Default for SegmentedProgress {
+    fn default() -> Self {
+        Self {
+            segments: 10,
+            gap: Val::Px(2.0),
+            partial: Default::default(),
+            filled_color: Color::GREEN.into(),
+            empty_color: Color::NONE.into(),
+        }
+    }
+}
+
+/// How [`SegmentedProgress`] displays a block that is partially done
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PartialSegment {
+    /// Fill the block if it's at least half done
+    #[default]
+    Round,
+    /// Fill the done part of the block
+    Fill,
+}
+
+/// A block spawned for [`SegmentedProgress`]
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ProgressSegment {
+    index: usize,
+}
+
+/// The filled part of a [`ProgressSegment`]
+#[derive(Component)]
+pub struct ProgressSegmentFill;
+
+/// How much of the `index`th block is filled, from 0.0 to 1.0
+fn segment_fill(segmented: &SegmentedProgress, fraction: f32, index: usize) -> f32 {
+    let done = fraction * segmented.segments as f32;
+    match segmented.partial {
+        PartialSegment::Round => {
+            if (index as f32) < done.round() {
+                1.0
+            } else {
+                0.0
+            }
+        }
+        PartialSegment::Fill => (done - index as f32).clamp(0.0, 1.0),
+    }
+}
+
+pub fn progress_bar_segments_create_system(
+    mut commands: Commands,
+    query: Query<
+        (
+            Entity,
+            &Progress,
+            &SegmentedProgress,
+            Option<&ProgressRange>,
+        ),
+        Added<SegmentedProgress>,
+    >,
+) {
+    for (entity, progress, segmented, range) in query.iter() {
+        let fraction = progress.fraction(&range.copied().unwrap_or_default());
+        commands.entity(entity).with_children(|parent| {
+            for index in 0..segmented.segments {
+                parent
+                    .spawn_bundle(NodeBundle {
+                        style: Style {
+                            size: Size::new(Val::Undefined, Val::Percent(100.0)),
+                            flex_grow: 1.0,
+                            flex_basis: Val::Px(0.0),
+                            margin: UiRect {
+                                left: if index == 0 {
+                                    Val::Undefined
+                                } else {
+                                    segmented.gap
+                                },
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        },
+                        color: segmented.empty_color,
+                        ..Default::default()
+                    })
+                    .insert(ProgressSegment { index })
+                    .with_children(|parent| {
+                        parent
+                            .spawn_bundle(NodeBundle {
+                                style: Style {
+                                    size: Size::new(
+                                        Val::Percent(
+                                            segment_fill(segmented, fraction, index) * 100.0,
+                                        ),
+                                        Val::Percent(100.0),
+                                    ),
+                                    ..Default::default()
+                                },
+                                color: segmented.filled_color,
+                                ..Default::default()
+                            })
+                            .insert(ProgressSegmentFill);
+                    });
+            }
+        });
+    }
+}
+
+pub fn progress_bar_segments_system(
+    query: Query<
+        (&Progress, &SegmentedProgress, Option<&ProgressRange>),
+        Or<(Changed<Progress>, Changed<SegmentedProgress>)>,
+    >,
+    mut segments: Query<(&Parent, &ProgressSegment, &mut UiColor), Without<ProgressSegmentFill>>,
+    mut fills: Query<(&Parent, &mut Style, &mut UiColor), With<ProgressSegmentFill>>,
+) {
+    for (parent, mut style, mut fill_color) in fills.iter_mut() {
+        let (segment_parent, segment, mut segment_color) = match segments.get_mut(parent.get()) {
+            Ok(segment) => segment,
+            Err(_) => continue,
+        };
+        let (progress, segmented, range) = match query.get(segment_parent.get()) {
+            Ok(bar) => bar,
+            Err(_) => continue,
+        };
+        let fraction = progress.fraction(&range.copied().unwrap_or_default());
+        style.size.width = Val::Percent(segment_fill(segmented, fraction, segment.index) * 100.0);
+        fill_color.0 = segmented.filled_color.0;
+        segment_color.0 = segmented.empty_color.0;
+    }
+}
+
+/// A secondary value displayed behind the [`Progress`], like the buffered part of a video.
+/// It's clamped the same way as the [`Progress`] of the bar and follows the same
+/// [`ProgressBarSizeAnimation`] direction. Vertical bars are filled from the bottom.
+///
+/// The buffered fill is spawned into the bar's parent (the track) behind the bar,
+/// in the bar's color at 40% opacity, so the bar must have a parent
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+pub struct BufferedProgress(pub f32);
+
+/// The node spawned for [`BufferedProgress`]
+#[derive(Component, Debug, Clone, Copy)]
+pub struct BufferedProgressFill {
+    bar: Entity,
+}
+
+pub fn progress_bar_buffered_create_system(
+    mut commands: Commands,
+    query: Query<(Entity, &Parent, &UiColor), Added<BufferedProgress>>,
+) {
+    for (bar, parent, color) in query.iter() {
+        let mut fill_color = color.0;
+        fill_color.set_a(color.0.a() * 0.4);
+        let fill = commands
+            .spawn_bundle(NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    ..Default::default()
+                },
+                color: fill_color.into(),
+                ..Default::default()
+            })
+            .insert(BufferedProgressFill { bar })
+            .id();
+        // the first child is drawn first, behind the bar
+        commands.entity(parent.get()).insert_children(0, &[fill]);
+    }
+}
+
+/// Displays the progress as a ring of slices that light up clockwise from the top,
+/// like a loading spinner. The slices are spawned as children when this component is added,
+/// so changing `count` later has no effect.
+///
+/// bevy_ui can only draw rectangles, so a continuous arc isn't possible: each slice is
+/// a rectangle rotated to point away from the center, and a slice is lit once the progress
+/// is at least half way through it. Sizes are percents of the node, so the node should be square
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_slimy_widgets::ProgressBarBundle;
+/// # use bevy_slimy_widgets::progress_bar::RadialProgressSegments;
+///
+/// fn setup(mut commands: Commands) {
+///     commands
+///         .spawn_bundle(ProgressBarBundle {
+///             style: Style {
+///                 size: Size::new(Val::Px(64.0), Val::Px(64.0)),
+///                 ..Default::default()
+///             },
+///             color: Color::NONE.into(),
+///             ..Default::default()
+///         })
+///         .insert(RadialProgressSegments {
+///             count: 8,
+///             ..Default::default()
+///         });
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Copy)]
+pub struct RadialProgressSegments {
+    /// Number of slices
+    pub count: usize,
+    /// Distance from the center of the node to the center of a slice, in percents of the node
+    pub radius: f32,
+    /// Length of a slice along the radius, in percents of the node
+    pub length: f32,
+    /// Width of a slice, in percents of the node
+    pub thickness: f32,
+    /// Color of the done slices
+    pub filled_color: UiColor,
+    /// Color of the rest of the slices
+    pub empty_color: UiColor,
+}
+
+impl Default for RadialProgressSegments {
+    fn default() -> Self {
+        Self {
+            count: 12,
+            radius: 35.0,
+            length: 25.0,
+            thickness: 8.0,
+            filled_color: Color::GREEN.into(),
+            empty_color: Color::rgba(1.0, 1.0, 1.0, 0.2).into(),
+        }
+    }
+}
+
+/// A slice spawned for [`RadialProgressSegments`]
+#[derive(Component, Debug, Clone, Copy)]
+pub struct RadialProgressSegment {
+    index: usize,
+}
+
+pub fn radial_progress_create_system(
+    mut commands: Commands,
+    query: Query<
+        (
+            Entity,
+            &Progress,
+            &RadialProgressSegments,
+            Option<&ProgressRange>,
+        ),
+        Added<RadialProgressSegments>,
+    >,
+) {
+    for (entity, progress, radial, range) in query.iter() {
+        let lit = radial_lit_count(
+            radial,
+            progress.fraction(&range.copied().unwrap_or_default()),
+        );
+        commands.entity(entity).with_children(|parent| {
+            for index in 0..radial.count {
+                // clockwise from the top
+                let angle = index as f32 / radial.count as f32 * std::f32::consts::TAU;
+                let center = Vec2::new(
+                    50.0 + radial.radius * angle.sin(),
+                    50.0 - radial.radius * angle.cos(),
+                );
+                parent
+                    .spawn_bundle(NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            position: UiRect {
+                                left: Val::Percent(center.x - radial.thickness / 2.0),
+                                top: Val::Percent(center.y - radial.length / 2.0),
+                                ..Default::default()
+                            },
+                            size: Size::new(
+                                Val::Percent(radial.thickness),
+                                Val::Percent(radial.length),
+                            ),
+                            ..Default::default()
+                        },
+                        color: if index < lit {
+                            radial.filled_color
+                        } else {
+                            radial.empty_color
+                        },
+                        // the layout only sets the translation, so the rotation is kept
+                        transform: Transform::from_rotation(Quat::from_rotation_z(-angle)),
+                        ..Default::default()
+                    })
+                    .insert(RadialProgressSegment { index });
+            }
+        });
+    }
+}
+
+pub fn radial_progress_system(
+    query: Query<
+        (&Progress, &RadialProgressSegments, Option<&ProgressRange>),
+        Or<(Changed<Progress>, Changed<RadialProgressSegments>)>,
+    >,
+    mut segments: Query<(&Parent, &RadialProgressSegment, &mut UiColor)>,
+) {
+    for (parent, segment, mut color) in segments.iter_mut() {
+        let (progress, radial, range) = match query.get(parent.get()) {
+            Ok(bar) => bar,
+            Err(_) => continue,
+        };
+        let lit = radial_lit_count(
+            radial,
+            progress.fraction(&range.copied().unwrap_or_default()),
+        );
+        color.0 = if segment.index < lit {
+            radial.filled_color.0
+        } else {
+            radial.empty_color.0
+        };
+    }
+}
+
+/// Number of slices that are lit at `fraction`
+fn radial_lit_count(radial: &RadialProgressSegments, fraction: f32) -> usize {
+    (fraction * radial.count as f32).round() as usize
+}
+
+/// Send this event to set [`Progress`] back to 0%, e.g. when a loading sequence is restarted.
+/// [`ProgressTarget`] is reset too, so [`ProgressRate`] doesn't move the bar back up,
+/// and [`ProgressBarThrottle`] and [`ProgressBarSmoothing`] display the empty bar immediately
+#[derive(Clone, Copy, Debug)]
+pub struct ResetProgress {
+    /// The progress bar entity
+    pub entity: Entity,
+}
+
+/// Resets every progress bar in `entities`, see [`ResetProgress`]
+pub fn reset_all(
+    events: &mut EventWriter<ResetProgress>,
+    entities: impl IntoIterator<Item = Entity>,
+) {
+    events.send_batch(entities.into_iter().map(|entity| ResetProgress { entity }));
+}
+
+/// Handles [`ResetProgress`] events
+pub fn progress_reset_system(
+    mut events: EventReader<ResetProgress>,
+    mut query: Query<(
+        &mut Progress,
+        Option<&mut ProgressTarget>,
+        Option<&mut ProgressBarThrottle>,
+        Option<&mut ProgressBarSmoothing>,
+    )>,
+) {
+    for event in events.iter() {
+        if let Ok((mut progress, target, throttle, smoothing)) = query.get_mut(event.entity) {
+            progress.set(0.0);
+            if let Some(mut target) = target {
+                target.0 = 0.0;
+            }
+            if let Some(mut throttle) = throttle {
+                let interval = throttle.timer.duration();
+                throttle.timer.set_elapsed(interval);
+            }
+            if let Some(mut smoothing) = smoothing {
+                smoothing.snap(0.0);
+            }
+        }
+    }
+}
+
+/// Keeps [`Progress`] in sync with resource `T`.
+/// The system for `T` must be registered with [`ProgressBindingAppExt::bind_progress`]
+#[derive(Component)]
+pub struct BindProgress<T> {
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for BindProgress<T> {
+    fn default() -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Registers systems that sync [`Progress`] with resources
+pub trait ProgressBindingAppExt {
+    /// Sets [`Progress`] of entities with [`BindProgress<T>`] to the value of resource `T` every frame
+    fn bind_progress<T: Into<f32> + Copy + Send + Sync + 'static>(&mut self) -> &mut Self;
+}
+
+impl ProgressBindingAppExt for App {
+    fn bind_progress<T: Into<f32> + Copy + Send + Sync + 'static>(&mut self) -> &mut Self {
+        self.add_system(
+            progress_bind_system::<T>
+                .label(crate::SlimyWidgetsSet::ProgressBar)
+                .before(crate::SystemLabels::ProgressRate),
+        )
+    }
+}
+
+/// Copies resource `T` into [`Progress`] of entities with [`BindProgress<T>`]
+pub fn progress_bind_system<T: Into<f32> + Copy + Send + Sync + 'static>(
+    resource: Option<Res<T>>,
+    mut query: Query<&mut Progress, With<BindProgress<T>>>,
+) {
+    let value = match resource {
+        Some(resource) => (*resource).into(),
+        None => return,
+    };
+    for mut progress in query.iter_mut() {
+        let mut bound = *progress;
+        bound.set(value);
+        if bound != *progress {
+            *progress = bound;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reset_snaps_smoothing() {
+        let mut app = App::new();
+        app.add_event::<ResetProgress>()
+            .add_system(progress_reset_system);
+        let mut smoothing = ProgressBarSmoothing::new(Duration::from_secs(1), Easing::Linear);
+        smoothing.retarget(0.8);
+        smoothing.tick(Duration::from_millis(500));
+        let bar = app
+            .world
+            .spawn()
+            .insert(Progress::new(80.0))
+            .insert(smoothing)
+            .id();
+        app.world.send_event(ResetProgress { entity: bar });
+        app.update();
+
+        assert_eq!(**app.world.get::<Progress>(bar).unwrap(), 0.0);
+        let smoothing = app.world.get::<ProgressBarSmoothing>(bar).unwrap();
+        assert_eq!(smoothing.displayed(), 0.0);
+        assert_eq!(smoothing.to, 0.0);
+        assert_eq!(smoothing.elapsed, smoothing.duration);
+    }
+
+    #[test]
+    fn from_hz_without_rate_is_unthrottled() {
+        for hz in [0.0, -5.0, f32::NAN] {
+            assert_eq!(
+                ProgressBarThrottle::from_hz(hz).timer.duration(),
+                Duration::ZERO
+            );
+        }
+        assert_eq!(
+            ProgressBarThrottle::from_hz(f32::INFINITY).timer.duration(),
+            Duration::ZERO
+        );
+        assert_eq!(
+            ProgressBarThrottle::from_hz(4.0).timer.duration(),
+            Duration::from_millis(250)
+        );
+    }
+
+    #[test]
+    fn track_content_size_subtracts_px_padding_and_border() {
+        let node = Node {
+            size: Vec2::new(200.0, 30.0),
+        };
+        let style = Style {
+            padding: UiRect {
+                left: Val::Px(4.0),
+                right: Val::Px(6.0),
+                top: Val::Px(2.0),
+                bottom: Val::Percent(10.0),
+            },
+            border: UiRect::all(Val::Px(1.0)),
+            ..Default::default()
+        };
+        // percent padding isn't subtracted
+        assert_eq!(track_content_size(&node, &style), Vec2::new(188.0, 26.0));
+    }
+
+    #[test]
+    fn edges_px_ignores_other_units() {
+        assert_eq!(edges_px(Val::Px(3.0), Val::Px(2.5)), 5.5);
+        assert_eq!(edges_px(Val::Auto, Val::Px(2.0)), 2.0);
+        assert_eq!(edges_px(Val::Percent(50.0), Val::Undefined), 0.0);
+    }
+
+    #[test]
+    fn smoothing_moves_to_target_over_duration() {
+        let mut smoothing = ProgressBarSmoothing::new(Duration::from_secs(2), Easing::Linear);
+        smoothing.retarget(0.8);
+        smoothing.tick(Duration::from_secs(1));
+        assert!((smoothing.displayed() - 0.4).abs() < 1e-6);
+        smoothing.tick(Duration::from_secs(5));
+        assert_eq!(smoothing.displayed(), 0.8);
+        assert_eq!(smoothing.elapsed, smoothing.duration);
+    }
+
+    #[test]
+    fn smoothing_retargets_from_displayed() {
+        let mut smoothing = ProgressBarSmoothing::new(Duration::from_secs(2), Easing::Linear);
+        smoothing.retarget(1.0);
+        smoothing.tick(Duration::from_secs(1));
+        smoothing.retarget(0.0);
+        assert_eq!(smoothing.from, 0.5);
+        smoothing.tick(Duration::from_secs(1));
+        assert!((smoothing.displayed() - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn smoothing_without_duration_jumps() {
+        let mut smoothing = ProgressBarSmoothing::new(Duration::ZERO, Easing::EaseInOut);
+        smoothing.retarget(0.3);
+        smoothing.tick(Duration::ZERO);
+        assert_eq!(smoothing.displayed(), 0.3);
+    }
+
+    #[test]
+    fn complete_fires_once() {
+        let mut app = App::new();
+        app.add_event::<ProgressComplete>()
+            .add_system(progress_complete_system);
+        let bar = app.world.spawn().insert(Progress::new(50.0)).id();
+        let mut reader = app
+            .world
+            .resource::<Events<ProgressComplete>>()
+            .get_reader();
+        let mut completed = |app: &App| {
+            let events = app.world.resource::<Events<ProgressComplete>>();
+            reader
+                .iter(events)
+                .map(|event| event.entity)
+                .collect::<Vec<_>>()
+        };
+        app.update();
+        assert_eq!(completed(&app), []);
+
+        app.world.get_mut::<Progress>(bar).unwrap().set(100.0);
+        app.update();
+        assert_eq!(completed(&app), [bar]);
+
+        // still done, even if changed again
+        app.update();
+        app.world.get_mut::<Progress>(bar).unwrap().set(100.0);
+        app.update();
+        assert_eq!(completed(&app), []);
+        assert!(app.world.get::<ProgressCompleteFired>(bar).is_some());
+    }
+
+    #[test]
+    fn throttled_label_waits_for_refresh() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .add_system(progress_bar_throttle_system.label("throttle"))
+            .add_system(progress_bar_label_system.after("throttle"));
+        let text = app
+            .world
+            .spawn()
+            .insert(ProgressBarLabelText)
+            .insert(Text::from_section("", TextStyle::default()))
+            .id();
+        let bar = app
+            .world
+            .spawn()
+            .insert(Progress::new(10.0))
+            .insert(ProgressBarLabel::default())
+            .insert(ProgressBarThrottle::new(Duration::from_secs(3600)))
+            .push_children(&[text])
+            .id();
+        let label = |app: &App| {
+            app.world.get::<Text>(text).unwrap().sections[0]
+                .value
+                .clone()
+        };
+
+        // the first change is displayed immediately
+        app.update();
+        assert_eq!(label(&app), "10%");
+
+        app.world.get_mut::<Progress>(bar).unwrap().set(50.0);
+        app.update();
+        assert_eq!(label(&app), "10%");
+    }
 }
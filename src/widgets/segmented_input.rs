@@ -0,0 +1,121 @@
+//! A PIN/OTP-style input that renders its value across separate cells.
+//! It's built on top of the text input components, so typing, backspace and arrows
+//! move between the cells the same way they move the cursor in a text input.
+
+use bevy::prelude::*;
+
+use crate::text_input::{
+    DefaultConstrains, InputTextStyle, TextInputConstrains, TextInputFocus, TextInputValue,
+};
+
+/// Splits the input into `cells` boxes, one character per box.
+/// Added as a component to [`SegmentedInputBundle`](crate::SegmentedInputBundle)
+#[derive(Component, Clone, Debug)]
+pub struct SegmentedInput {
+    /// Number of cells. The value's length is limited to this number
+    pub cells: usize,
+    /// A box that will be spawned for each cell
+    pub cell: NodeBundle,
+    /// Color of the cell with the cursor
+    pub active_color: UiColor,
+}
+
+impl SegmentedInput {
+    /// Square dark gray cells, the cell with the cursor is highlighted
+    pub fn new(cells: usize) -> Self {
+        Self {
+            cells,
+            cell: NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Px(40.0), Val::Px(40.0)),
+                    margin: UiRect::all(Val::Px(4.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                color: Color::DARK_GRAY.into(),
+                ..Default::default()
+            },
+            active_color: Color::GRAY.into(),
+        }
+    }
+}
+
+impl Default for SegmentedInput {
+    fn default() -> Self {
+        Self::new(4)
+    }
+}
+
+/// A cell of [`SegmentedInput`] that displays the character with this index
+#[derive(Component, Clone, Copy, Debug)]
+pub struct SegmentedInputCell(pub usize);
+
+/// Spawns the cells and limits the value's length
+pub fn segmented_input_create_system(
+    mut commands: Commands,
+    mut query: Query<
+        (
+            Entity,
+            &SegmentedInput,
+            &InputTextStyle,
+            &TextInputValue,
+            &mut TextInputConstrains,
+        ),
+        Added<SegmentedInput>,
+    >,
+) {
+    for (entity, segmented, style, value, mut constrains) in query.iter_mut() {
+        constrains.push(DefaultConstrains::MaxChars(segmented.cells));
+        commands.entity(entity).with_children(|parent| {
+            for index in 0..segmented.cells {
+                parent
+                    .spawn_bundle(segmented.cell.clone())
+                    .insert(SegmentedInputCell(index))
+                    .with_children(|parent| {
+                        parent.spawn_bundle(TextBundle {
+                            text: Text::from_section(cell_text(value, index), style.0.clone()),
+                            ..Default::default()
+                        });
+                    });
+            }
+        });
+    }
+}
+
+/// Updates cells' characters and highlights the cell with the cursor
+pub fn segmented_input_update_system(
+    query: Query<
+        (Entity, &SegmentedInput, &TextInputValue, &TextInputFocus),
+        Or<(Changed<TextInputValue>, Changed<TextInputFocus>)>,
+    >,
+    mut cells: Query<(&Parent, &SegmentedInputCell, &mut UiColor, &Children)>,
+    mut texts: Query<&mut Text>,
+) {
+    for (entity, segmented, value, focus) in query.iter() {
+        let active_cell = focus
+            .0
+            .map(|cursor| cursor.min(segmented.cells.saturating_sub(1)));
+        for (_, cell, mut color, children) in cells
+            .iter_mut()
+            .filter(|(parent, ..)| parent.get() == entity)
+        {
+            *color = if active_cell == Some(cell.0) {
+                segmented.active_color
+            } else {
+                segmented.cell.color
+            };
+            if let Ok(mut text) = texts.get_mut(children[0]) {
+                text.sections[0].value = cell_text(value, cell.0);
+            }
+        }
+    }
+}
+
+fn cell_text(value: &str, index: usize) -> String {
+    value
+        .chars()
+        .nth(index)
+        .map(String::from)
+        .unwrap_or_default()
+}
@@ -1,13 +1,21 @@
-use std::ops::Deref;
+use std::borrow::Cow;
+use std::ops::{Deref, Range};
 use std::time::Duration;
 
 use ab_glyph::{Font as AbGlyphFont, FontArc, Glyph, PxScale, ScaleFont};
+use bevy::ecs::system::Command;
 use bevy::input::keyboard::KeyboardInput;
 use bevy::input::ButtonState;
 use bevy::prelude::*;
 use bevy::text::{Font, HorizontalAlign, Text, TextAlignment, TextStyle, VerticalAlign};
+use bevy::ui::FocusPolicy;
+use bevy::utils::{HashMap, HashSet};
+use bevy::window::FileDragAndDrop;
+#[cfg(feature = "clipboard")]
 use clipboard::{ClipboardContext, ClipboardProvider};
-use glyph_brush::{GlyphCalculatorBuilder, GlyphCruncher, Section};
+use glyph_brush::{FontId, GlyphCalculator, GlyphCalculatorBuilder, GlyphCruncher, Section};
+
+use crate::progress_bar::Easing;
 
 /// A list of [`TextInputConstrain`]s. The character won't be added to the
 /// input if any of these returns false
@@ -15,14 +23,196 @@ use glyph_brush::{GlyphCalculatorBuilder, GlyphCruncher, Section};
 pub struct TextInputConstrains(pub Vec<Box<dyn TextInputConstrain + Send + Sync + 'static>>);
 
 impl TextInputConstrains {
+    /// Returns true if all constrains allow changing `old` value to `new`
     pub fn test(&self, old: &str, new: &str) -> bool {
         self.0.iter().all(|constrain| constrain.test(old, new))
     }
+
+    /// Returns `Some` if any constrain rejects changing `old` value to `new`,
+    /// with the [`reason`](TextInputConstrain::reason) of the first one that does
+    /// ```
+    /// # use bevy_slimy_widgets::text_input::{DefaultConstrains, TextInputConstrains};
+    ///
+    /// let constrains = TextInputConstrains::default().with(DefaultConstrains::MaxLength(3));
+    /// assert_eq!(constrains.rejection("abc", "abc"), None);
+    /// assert!(constrains.rejection("abc", "abcd").is_some());
+    /// ```
+    pub fn rejection(&self, old: &str, new: &str) -> Option<Option<String>> {
+        self.0
+            .iter()
+            .find(|constrain| !constrain.test(old, new))
+            .map(|constrain| constrain.reason(old, new))
+    }
+
+    /// Fits `pasted` into `value` at byte index `at`, where `old` is the value before this frame's
+    /// edits. Returns the text to paste and the dropped characters, depending on each constrain's
+    /// [`paste_behavior`](TextInputConstrain::paste_behavior)
+    /// ```
+    /// # use bevy_slimy_widgets::text_input::{DefaultConstrains, TextInputConstrains};
+    ///
+    /// let constrains = TextInputConstrains::default().with(DefaultConstrains::MaxLength(5));
+    /// let (pasted, dropped) = constrains.fit_paste("", "", 0, "hello, world");
+    /// assert_eq!(pasted, "hello");
+    /// assert_eq!(dropped, ", world");
+    ///
+    /// let constrains = TextInputConstrains::default()
+    ///     .with(DefaultConstrains::AllowedCharacters(('0'..='9').collect()));
+    /// let (pasted, dropped) = constrains.fit_paste("", "", 0, "+1 (555) 010");
+    /// assert_eq!(pasted, "1555010");
+    /// assert_eq!(dropped, "+ () ");
+    /// ```
+    ///
+    /// Filtering tests whole chunks of the pasted text and truncation binary-searches its length,
+    /// so both assume that if some text is rejected, any text containing it is rejected too,
+    /// as with the length and character constrains
+    pub fn fit_paste(&self, old: &str, value: &str, at: usize, pasted: &str) -> (String, String) {
+        let with_pasted = |text: &str| {
+            let mut new = String::with_capacity(value.len() + text.len());
+            new.push_str(&value[..at]);
+            new.push_str(text);
+            new.push_str(&value[at..]);
+            new
+        };
+        let filters: Vec<_> = self
+            .0
+            .iter()
+            .filter(|constrain| constrain.paste_behavior() == PasteBehavior::Filter)
+            .collect();
+        let mut kept = String::new();
+        let mut dropped = String::new();
+        if filters.is_empty() {
+            kept.push_str(pasted);
+        } else {
+            // try whole chunks and split only the rejected ones, down to single characters
+            let mut new = value.to_string();
+            let mut insert_at = at;
+            let mut chunks = vec![pasted];
+            while let Some(chunk) = chunks.pop() {
+                new.insert_str(insert_at, chunk);
+                if filters.iter().all(|constrain| constrain.test(old, &new)) {
+                    kept.push_str(chunk);
+                    insert_at += chunk.len();
+                    continue;
+                }
+                new.replace_range(insert_at..insert_at + chunk.len(), "");
+                match chunk.char_indices().nth(chunk.chars().count() / 2) {
+                    Some((mid, _)) if mid > 0 => {
+                        chunks.push(&chunk[mid..]);
+                        chunks.push(&chunk[..mid]);
+                    }
+                    _ => dropped.push_str(chunk),
+                }
+            }
+        }
+        let new = with_pasted(&kept);
+        let rejected = self.0.iter().any(|constrain| {
+            constrain.paste_behavior() == PasteBehavior::Reject && !constrain.test(old, &new)
+        });
+        if rejected {
+            return (String::new(), pasted.to_string());
+        }
+        if self.test(old, &new) {
+            return (kept, dropped);
+        }
+        // paste as much as the constrains allow
+        let boundaries: Vec<usize> = kept.char_indices().map(|(i, _)| i).collect();
+        let fitting_chars =
+            boundaries.partition_point(|&len| self.test(old, &with_pasted(&kept[..len])));
+        let fitting = fitting_chars
+            .checked_sub(1)
+            .map_or(0, |last| boundaries[last]);
+        dropped.push_str(&kept[fitting..]);
+        kept.truncate(fitting);
+        (kept, dropped)
+    }
+
+    /// Adds a constrain, keeping the existing ones
+    pub fn push(&mut self, constrain: impl TextInputConstrain + Send + Sync + 'static) {
+        self.0.push(Box::new(constrain));
+    }
+
+    /// Builder-style [`push`](Self::push)
+    /// ```
+    /// # use bevy_slimy_widgets::text_input::{DefaultConstrains, TextInputConstrains};
+    ///
+    /// // disallows new lines and limits the length
+    /// let constrains = TextInputConstrains::default().with(DefaultConstrains::MaxLength(13));
+    /// assert!(constrains.test("", "hello"));
+    /// assert!(!constrains.test("", "hello\nworld"));
+    /// assert!(!constrains.test("", "hello, world!!"));
+    /// ```
+    pub fn with(mut self, constrain: impl TextInputConstrain + Send + Sync + 'static) -> Self {
+        self.push(constrain);
+        self
+    }
+
+    /// Removes the new line constrain that [`TextInputConstrains::default`] adds
+    /// ```
+    /// # use bevy_slimy_widgets::text_input::TextInputConstrains;
+    ///
+    /// let mut constrains = TextInputConstrains::default();
+    /// constrains.remove_default_newline();
+    /// assert!(constrains.test("", "hello\nworld"));
+    /// ```
+    pub fn remove_default_newline(&mut self) {
+        self.0.retain(|constrain| {
+            !matches!(
+                (**constrain).as_any().downcast_ref::<DefaultConstrains>(),
+                Some(DefaultConstrains::DisallowedCharacters(chars)) if chars == &['\n']
+            )
+        });
+    }
+}
+
+impl Default for TextInputConstrains {
+    /// Disallows new lines
+    fn default() -> Self {
+        Self(vec![Box::new(DefaultConstrains::DisallowedCharacters(
+            vec!['\n'],
+        ))])
+    }
+}
+
+mod private {
+    use std::any::Any;
+
+    pub trait AsAny {
+        fn as_any(&self) -> &dyn Any;
+    }
+
+    impl<T: Any> AsAny for T {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
 }
 
-pub trait TextInputConstrain {
+pub trait TextInputConstrain: private::AsAny {
     /// Returns true if the character(s) can be appended/inserted to the input field
     fn test(&self, old: &str, new: &str) -> bool;
+
+    /// Explains why [`test`](Self::test) rejected the change, to show to the user.
+    /// Sent in [`TextInputRejected`]
+    fn reason(&self, _old: &str, _new: &str) -> Option<String> {
+        None
+    }
+
+    /// What happens to pasted text that this constrain rejects.
+    /// Default is [`PasteBehavior::Truncate`]
+    fn paste_behavior(&self) -> PasteBehavior {
+        PasteBehavior::Truncate
+    }
+}
+
+/// How [`TextInputConstrains::fit_paste`] handles pasted text that a constrain rejects
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PasteBehavior {
+    /// Paste the longest start of the text that is allowed
+    Truncate,
+    /// Drop the characters that aren't allowed and paste the rest
+    Filter,
+    /// Don't paste anything
+    Reject,
 }
 
 /// Default text input constrains
@@ -32,8 +222,57 @@ pub enum DefaultConstrains {
     AllowedCharacters(Vec<char>),
     /// Disallow these characters
     DisallowedCharacters(Vec<char>),
-    /// Max input length
+    /// Max input length in bytes
     MaxLength(usize),
+    /// Max input length in characters, e.g. for one character per cell
+    MaxChars(usize),
+    /// The whole new value must match this pattern. Anchor it with `^` and `$`,
+    /// and allow the values that are typed on the way to a complete one
+    /// ```
+    /// # use bevy_slimy_widgets::text_input::{DefaultConstrains, TextInputConstrains};
+    /// # use regex::Regex;
+    ///
+    /// let constrains = TextInputConstrains::default()
+    ///     .with(DefaultConstrains::Regex(Regex::new(r"^\d{0,4}$").unwrap()));
+    /// assert!(constrains.test("", "12"));
+    /// assert!(constrains.test("123", "1234"));
+    /// assert!(!constrains.test("1234", "12345"));
+    /// assert!(!constrains.test("12", "12a"));
+    /// ```
+    #[cfg(feature = "regex")]
+    Regex(regex::Regex),
+    /// A number between `min` and `max`, with a fractional part if `allow_decimal` is true.
+    ///
+    /// Values on the way to a valid number are allowed while typing: `""`, `"-"` if `min` is
+    /// negative, and a trailing `"."`. A number counts as in range if typing more digits could
+    /// bring it into the range, so with `min: 10.0` the value `"1"` is allowed. Check the final
+    /// value when it's submitted
+    /// ```
+    /// # use bevy_slimy_widgets::text_input::{DefaultConstrains, TextInputConstrains};
+    ///
+    /// let constrains = TextInputConstrains::default().with(DefaultConstrains::NumberRange {
+    ///     min: -100.0,
+    ///     max: 100.0,
+    ///     allow_decimal: true,
+    /// });
+    /// let mut value = String::new();
+    /// for ch in "-12.5".chars() {
+    ///     let new = format!("{value}{ch}");
+    ///     assert!(constrains.test(&value, &new));
+    ///     value = new;
+    /// }
+    /// assert!(!constrains.test("-12.5", "-12.5.")); // malformed
+    /// assert!(!constrains.test("-12", "-120")); // out of range
+    /// assert!(!constrains.test("", "1e3"));
+    /// ```
+    NumberRange {
+        /// The smallest allowed number
+        min: f64,
+        /// The largest allowed number
+        max: f64,
+        /// Whether the number can have a fractional part
+        allow_decimal: bool,
+    },
 }
 
 impl TextInputConstrain for DefaultConstrains {
@@ -46,6 +285,62 @@ impl TextInputConstrain for DefaultConstrains {
                 !new.chars().any(|ch| chars.contains(&ch))
             }
             DefaultConstrains::MaxLength(len) => new.len() <= *len,
+            DefaultConstrains::MaxChars(len) => new.chars().count() <= *len,
+            #[cfg(feature = "regex")]
+            DefaultConstrains::Regex(regex) => regex.is_match(new),
+            DefaultConstrains::NumberRange {
+                min,
+                max,
+                allow_decimal,
+            } => {
+                let digits = new.strip_prefix('-').unwrap_or(new);
+                let well_formed = digits.chars().all(|ch| ch.is_ascii_digit() || ch == '.')
+                    && digits.matches('.').count() <= usize::from(*allow_decimal);
+                if !well_formed {
+                    return false;
+                }
+                let negative = digits.len() != new.len();
+                match digits.trim_end_matches('.') {
+                    "" => !negative || *min < 0.0,
+                    digits => {
+                        // more digits only move the number away from zero
+                        let number = digits.parse::<f64>().unwrap_or_default();
+                        if negative {
+                            -number >= *min
+                        } else {
+                            number <= *max
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn reason(&self, _old: &str, new: &str) -> Option<String> {
+        match self {
+            DefaultConstrains::AllowedCharacters(chars) => new
+                .chars()
+                .find(|ch| !chars.contains(ch))
+                .map(|ch| format!("{ch:?} isn't allowed")),
+            DefaultConstrains::DisallowedCharacters(chars) => new
+                .chars()
+                .find(|ch| chars.contains(ch))
+                .map(|ch| format!("{ch:?} isn't allowed")),
+            DefaultConstrains::MaxLength(len) => Some(format!("The limit is {len} bytes")),
+            DefaultConstrains::MaxChars(len) => Some(format!("The limit is {len} characters")),
+            #[cfg(feature = "regex")]
+            DefaultConstrains::Regex(_) => Some("The value doesn't match the format".to_string()),
+            DefaultConstrains::NumberRange { min, max, .. } => {
+                Some(format!("Enter a number from {min} to {max}"))
+            }
+        }
+    }
+
+    fn paste_behavior(&self) -> PasteBehavior {
+        match self {
+            DefaultConstrains::AllowedCharacters(_)
+            | DefaultConstrains::DisallowedCharacters(_) => PasteBehavior::Filter,
+            _ => PasteBehavior::Truncate,
         }
     }
 }
@@ -60,6 +355,11 @@ pub struct InputTextStyle(pub TextStyle);
 #[derive(Default, Component, Clone, Debug)]
 pub struct InputTextAlignment(pub TextAlignment);
 
+/// Overrides the alignment of [`PlaceholderText`].
+/// Without it, the placeholder uses [`InputTextAlignment`] so it doesn't jump when typing starts
+#[derive(Component, Clone, Debug)]
+pub struct PlaceholderAlignment(pub TextAlignment);
+
 impl From<Text> for PlaceholderText {
     fn from(inner: Text) -> Self {
         Self(inner)
@@ -78,14 +378,123 @@ impl From<TextAlignment> for InputTextAlignment {
     }
 }
 
-/// If present, it will decrease font size to fit into target size
-#[derive(Component, Default, Copy, Clone)]
-pub struct TextInputTargetSize(pub Option<Size>);
+impl From<TextAlignment> for PlaceholderAlignment {
+    fn from(inner: TextAlignment) -> Self {
+        Self(inner)
+    }
+}
+
+/// If `size` is present, it will decrease font size to fit into target size.
+///
+/// [`SystemLabels::TextInputFontDecrease`](crate::SystemLabels::TextInputFontDecrease) measures
+/// the value at [`InputTextStyle`]'s font size, the width of the longest line and the height
+/// of all lines, and shrinks the displayed font by the same factor so that both fit.
+/// [`InputTextStyle`] itself isn't changed, so the font grows back when the value gets shorter
+#[derive(Component, Default, Copy, Clone, Debug)]
+pub struct TextInputTargetSize {
+    /// The size the text should fit into
+    pub size: Option<Size>,
+    /// The font won't shrink below this size, the text will overflow instead
+    pub min_font_size: f32,
+    /// The font won't be bigger than this size, even if [`InputTextStyle`]'s font size is bigger
+    pub max_font_size: Option<f32>,
+}
+
+impl TextInputTargetSize {
+    /// Shrink the font to fit into `size` without any font size limits
+    pub fn new(size: Size) -> Self {
+        Self {
+            size: Some(size),
+            ..Default::default()
+        }
+    }
+
+    /// The font size to display text that measures `text_size` at `font_size`,
+    /// so that it fits into [`size`](Self::size) within the font size limits
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_slimy_widgets::text_input::TextInputTargetSize;
+    ///
+    /// let target_size = TextInputTargetSize {
+    ///     size: Some(Size::new(200.0, 30.0)),
+    ///     min_font_size: 8.0,
+    ///     max_font_size: None,
+    /// };
+    /// // a short value keeps its font size
+    /// assert_eq!(target_size.fit_font_size(24.0, Vec2::new(150.0, 29.0)), 24.0);
+    /// // a long value shrinks to half to fit
+    /// assert_eq!(target_size.fit_font_size(24.0, Vec2::new(400.0, 29.0)), 12.0);
+    /// // but not below the minimum
+    /// assert_eq!(target_size.fit_font_size(24.0, Vec2::new(1600.0, 29.0)), 8.0);
+    /// ```
+    pub fn fit_font_size(&self, font_size: f32, text_size: Vec2) -> f32 {
+        let factor = match self.size {
+            Some(size) => (text_size.x / size.width)
+                .max(text_size.y / size.height)
+                .max(1.0),
+            None => 1.0,
+        };
+        (font_size / factor)
+            .min(self.max_font_size.unwrap_or(f32::INFINITY))
+            .max(self.min_font_size)
+    }
+}
+
+/// Whether Enter inserts a new line
+#[derive(Component, Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum InputMode {
+    /// Enter doesn't change the value
+    #[default]
+    SingleLine,
+    /// Enter inserts a new line. The new line constrain of [`TextInputConstrains::default`]
+    /// is removed, so it doesn't reject Enter
+    Multiline,
+}
+
+pub fn text_input_mode_system(
+    mut query: Query<(&InputMode, &mut TextInputConstrains), Changed<InputMode>>,
+) {
+    for (mode, mut constrains) in query.iter_mut() {
+        // single-line inputs don't need the constrain: Enter doesn't insert anything
+        // and new lines are dropped from pasted text
+        if *mode == InputMode::Multiline {
+            constrains.remove_default_newline();
+        }
+    }
+}
 
-/// If the text input is focused, it will hold cursor index
+/// If the text input is focused, it will hold cursor index, counted in characters (not bytes)
 #[derive(Component, Default, Debug, Clone)]
 pub struct TextInputFocus(pub Option<usize>);
 
+/// The other end of the selection, the first one being the cursor.
+/// Shift with the cursor movement keys selects text, typing replaces the selected text
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TextInputSelection {
+    /// Character index where the selection started, if anything is selected
+    pub anchor: Option<usize>,
+}
+
+impl TextInputSelection {
+    /// Selected characters, given the cursor position
+    pub fn range(&self, cursor: usize) -> Option<Range<usize>> {
+        self.anchor
+            .filter(|&anchor| anchor != cursor)
+            .map(|anchor| anchor.min(cursor)..anchor.max(cursor))
+    }
+}
+
+/// Color of the selected text highlight. It's drawn behind the text, so an opaque color
+/// doesn't hide the selected characters
+#[derive(Component, Clone, Copy, Debug)]
+pub struct SelectionColor(pub UiColor);
+
+impl Default for SelectionColor {
+    fn default() -> Self {
+        Self(Color::rgba(0.2, 0.4, 1.0, 0.4).into())
+    }
+}
+
 /// A blinking thing that appears when you focus on a text input.
 /// A bundle that will be spawned with [`TextCursor`] component.
 /// Added as a component to `TextInputBundle`
@@ -114,15 +523,16 @@ impl TextCursorStyle {
                     } else {
                         Val::Undefined
                     },
-                    top: if alignment.vertical == VerticalAlign::Top {
-                        padding.top
-                    } else {
-                        Val::Undefined
+                    // auto margins on both sides center the cursor on the vertically centered line
+                    top: match alignment.vertical {
+                        VerticalAlign::Top => padding.top,
+                        VerticalAlign::Center => Val::Auto,
+                        VerticalAlign::Bottom => Val::Undefined,
                     },
-                    bottom: if alignment.vertical == VerticalAlign::Bottom {
-                        padding.bottom
-                    } else {
-                        Val::Undefined
+                    bottom: match alignment.vertical {
+                        VerticalAlign::Bottom => padding.bottom,
+                        VerticalAlign::Center => Val::Auto,
+                        VerticalAlign::Top => Val::Undefined,
                     },
                 },
                 ..Default::default()
@@ -133,9 +543,48 @@ impl TextCursorStyle {
     }
 }
 
+/// Shape of the [`TextCursor`], sized by the character after the cursor.
+/// Without this component, the cursor keeps the size from [`TextCursorStyle`]
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CursorShape {
+    /// A thin vertical bar before the character, like the default [`TextCursorStyle`]
+    #[default]
+    Bar,
+    /// A box over the whole character. Use a translucent color to keep the character visible
+    Block,
+    /// A line under the character, at the baseline
+    Underline,
+}
+
+/// Makes the [`TextCursor`] glide to its new position over `duration` instead of jumping.
+/// Selection highlights are separate nodes behind the text and move to the new selection at once
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CursorMovementAnimation {
+    /// How long the cursor moves to the new position
+    pub duration: Duration,
+}
+
+impl Default for CursorMovementAnimation {
+    fn default() -> Self {
+        Self {
+            duration: Duration::from_millis(80),
+        }
+    }
+}
+
+/// Where a [`TextCursor`] with [`CursorMovementAnimation`] is moving
+#[derive(Component, Clone, Copy, Debug)]
+pub struct CursorGlide {
+    from: Vec2,
+    to: Vec2,
+    elapsed: Duration,
+}
+
 #[derive(Component, Clone, Default, Debug)]
 pub struct TextCursor;
 
+/// Text field's value. It can be changed directly, the cursor and the selection
+/// are moved back into the value if it gets shorter than them
 #[derive(Component, Clone, Default, Debug)]
 pub struct TextInputValue(pub String);
 
@@ -147,364 +596,702 @@ impl Deref for TextInputValue {
     }
 }
 
-pub fn text_input_unfocus_system(
-    input: Res<Input<MouseButton>>,
-    mut text_inputs: Query<&mut TextInputFocus>,
-) {
-    if input.just_pressed(MouseButton::Left) {
-        for mut focus in text_inputs.iter_mut() {
-            focus.0 = None;
+/// A snapshot of everything that can be edited in a text input, e.g. for saving editor sessions
+/// ```
+/// # use bevy_slimy_widgets::text_input::{
+/// #     TextInputFocus, TextInputSelection, TextInputState, TextInputValue,
+/// # };
+/// let state = TextInputState {
+///     value: "hello".to_string(),
+///     cursor: Some(10),
+///     selection: Some((1, 10)),
+/// };
+/// let mut value = TextInputValue::default();
+/// let mut focus = TextInputFocus::default();
+/// let mut selection = TextInputSelection::default();
+/// state.apply(&mut value, &mut focus, Some(&mut selection));
+/// assert_eq!(focus.0, Some(5));
+/// assert_eq!(selection.anchor, Some(1));
+///
+/// let captured = TextInputState::capture(&value, &focus, Some(&selection));
+/// assert_eq!(captured.value, "hello");
+/// assert_eq!(captured.selection, Some((1, 5)));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TextInputState {
+    /// The value
+    pub value: String,
+    /// The cursor position, if the input is focused
+    pub cursor: Option<usize>,
+    /// The selection as `(anchor, cursor)`, if anything is selected.
+    /// Its cursor end takes precedence over [`cursor`](Self::cursor)
+    pub selection: Option<(usize, usize)>,
+}
+
+impl TextInputState {
+    /// Takes a snapshot of a text input
+    pub fn capture(
+        value: &TextInputValue,
+        focus: &TextInputFocus,
+        selection: Option<&TextInputSelection>,
+    ) -> Self {
+        Self {
+            value: value.0.clone(),
+            cursor: focus.0,
+            selection: selection
+                .and_then(|selection| selection.anchor)
+                .zip(focus.0),
+        }
+    }
+
+    /// Restores the snapshot. The cursor and the selection are clamped to the value,
+    /// the selection is dropped if there's no cursor
+    pub fn apply(
+        &self,
+        value: &mut TextInputValue,
+        focus: &mut TextInputFocus,
+        selection: Option<&mut TextInputSelection>,
+    ) {
+        let len = self.value.chars().count();
+        let cursor = self
+            .selection
+            .map(|(_, cursor)| cursor)
+            .or(self.cursor)
+            .map(|cursor| cursor.min(len));
+        let anchor = self
+            .selection
+            .filter(|_| cursor.is_some())
+            .map(|(anchor, _)| anchor.min(len));
+        if value.0 != self.value {
+            value.0 = self.value.clone();
+        }
+        if focus.0 != cursor {
+            focus.0 = cursor;
+        }
+        if let Some(selection) = selection {
+            if selection.anchor != anchor {
+                selection.anchor = anchor;
+            }
         }
     }
 }
 
-pub fn text_input_focus_on_click_system(
-    mut query: Query<(&mut TextInputFocus, &Interaction, &TextInputValue), Changed<Interaction>>,
+/// Colors the text input node differently depending on whether it's focused.
+/// Insert it alongside [`TextInputBundle`](crate::TextInputBundle)
+#[derive(Component, Clone, Copy, Debug)]
+pub struct FocusStyle {
+    /// Node color while the text input is focused
+    pub focused: UiColor,
+    /// Node color while the text input is not focused
+    pub unfocused: UiColor,
+}
+
+pub fn text_input_unfocus_system(
+    input: Res<Input<MouseButton>>,
+    mut text_inputs: Query<(
+        Entity,
+        &mut TextInputFocus,
+        Option<&mut TextInputSelection>,
+        Option<&Interaction>,
+    )>,
+    hit_areas: Query<(&Parent, &Interaction), With<TextInputHitArea>>,
 ) {
-    for (mut focus, interaction, value) in query.iter_mut() {
-        if *interaction == Interaction::Clicked {
-            focus.0 = Some(value.len());
+    if input.just_pressed(MouseButton::Left) {
+        for (entity, mut focus, selection, interaction) in text_inputs.iter_mut() {
+            let clicked = interaction == Some(&Interaction::Clicked)
+                || hit_areas.iter().any(|(parent, interaction)| {
+                    parent.get() == entity && *interaction == Interaction::Clicked
+                });
+            if clicked {
+                // the click systems move the cursor of a clicked input that is already focused
+                continue;
+            }
+            if focus.0.is_some() {
+                focus.0 = None;
+            }
+            if let Some(mut selection) = selection {
+                if selection.anchor.is_some() {
+                    selection.anchor = None;
+                }
+            }
         }
     }
 }
 
-pub fn text_input_move_cursor_system(
+pub fn text_input_focus_on_click_system(
     mut commands: Commands,
     fonts: Res<Assets<Font>>,
-    query: Query<
+    mut glyph_calculators: ResMut<GlyphCalculatorCache>,
+    windows: Res<Windows>,
+    time: Res<Time>,
+    multi_click_interval: Res<MultiClickInterval>,
+    mut query: Query<
         (
             Entity,
-            &TextInputFocus,
+            &mut TextInputFocus,
+            &Interaction,
             &TextInputValue,
-            &TextCursorStyle,
-            &CursorBlinkingInterval,
+            &InputTextStyle,
+            Option<&FallbackFonts>,
+            Option<&TextInputMask>,
+            Option<&mut TextInputSelection>,
+            Option<&TextInputStatus>,
+            Option<&TextInputClicks>,
+            Option<&SelectAllOnFocus>,
         ),
-        Changed<TextInputFocus>,
+        Changed<Interaction>,
     >,
-    query_text: Query<(&Parent, &Text), With<TextInputInner>>,
-    mut query_cursors: Query<(Entity, &mut Style, &Parent), With<TextCursor>>,
+    query_text: Query<(&Parent, &Text, &Node, &GlobalTransform), With<TextInputInner>>,
 ) {
-    'text: for (entity, focus, value, cursor_style, cursor_interval) in query.iter() {
-        if let Some(char_index) = focus.0 {
-            for (_, mut style, parent) in query_cursors.iter_mut() {
-                if parent.get() == entity {
-                    let text = query_text
-                        .iter()
-                        .find(|(parent, _)| parent.get() == entity)
-                        .unwrap()
-                        .1;
-                    let font = fonts
-                        .get(&text.sections[0].style.font)
-                        .unwrap()
-                        .font
-                        .clone();
-
-                    let text_before_cursor = &value.0[..char_index];
-                    let font_size = text.sections[0].style.font_size;
-                    let scale = PxScale {
-                        x: font_size,
-                        y: font_size,
-                    };
-                    let x = GlyphCalculatorBuilder::using_font(font.clone())
-                        .build()
-                        .cache_scope()
-                        .glyph_bounds(
-                            Section::default().add_text(
-                                glyph_brush::Text::new(
-                                    text_before_cursor.split('\n').last().unwrap(),
-                                )
-                                .with_scale(scale),
-                            ),
-                        )
-                        .map(|rect| rect.width())
-                        .unwrap_or_default();
-                    let lines_before_cursor = text_before_cursor.split('\n').count();
-                    let lines_total = value.0.split('\n').count();
-                    let y =
-                        font.as_scaled(scale).height() * (lines_total - lines_before_cursor) as f32;
-                    style.position.left = Val::Px(x);
-                    style.position.top = Val::Px(-y);
+    let window = windows.get_primary();
+    let scale_factor = window.map_or(1.0, |window| window.scale_factor() as f32);
+    let cursor_position = window.and_then(|window| window.cursor_position());
+    for (
+        entity,
+        mut focus,
+        interaction,
+        value,
+        text_style,
+        fallback_fonts,
+        mask,
+        selection,
+        status,
+        clicks,
+        select_all,
+    ) in query.iter_mut()
+    {
+        if *interaction == Interaction::Clicked && status != Some(&TextInputStatus::Disabled) {
+            let now = time.seconds_since_startup();
+            let count = match clicks {
+                Some(clicks) if now - clicks.last_click <= multi_click_interval.0.as_secs_f64() => {
+                    clicks.count + 1
+                }
+                _ => 1,
+            };
+            commands.entity(entity).insert(TextInputClicks {
+                last_click: now,
+                count,
+            });
 
-                    let current_glyph_bounds = font.glyph_bounds(&Glyph {
-                        id: font.glyph_id(value.0.chars().nth(char_index).unwrap_or(' ')),
-                        scale,
-                        position: Default::default(),
+            let clicked_index = cursor_position.and_then(|cursor_position| {
+                let (_, text, node, transform) = query_text
+                    .iter()
+                    .find(|(parent, ..)| parent.get() == entity)?;
+                let calculator =
+                    glyph_calculators.get(&fonts, &text_style.0.font, fallback_fonts)?;
+                // measure in physical pixels, the same way the cursor is positioned
+                let physical_scale = PxScale::from(text.sections[0].style.font_size * scale_factor);
+                Some(cursor_index_at(
+                    &masked(&value.0, mask),
+                    calculator,
+                    physical_scale,
+                    &text.alignment,
+                    node.size * scale_factor,
+                    local_text_position(cursor_position, node, transform) * scale_factor,
+                ))
+            });
+            let value = masked(&value.0, mask);
+            let select_all = focus.0.is_none() && select_all.is_some_and(|select| select.0);
+            let (anchor, cursor) = match clicked_index {
+                _ if select_all => (Some(0), value.chars().count()),
+                // 1st, 4th, 7th... click places the cursor and starts a drag
+                Some(clicked_index) if count % 3 == 1 => {
+                    commands.entity(entity).insert(TextInputDrag {
+                        start: clicked_index,
+                        current: clicked_index,
                     });
-                    if cursor_style.0.style.size.width == Val::Auto {
-                        style.size.width = Val::Px(current_glyph_bounds.width());
-                    }
-                    if cursor_style.0.style.size.height == Val::Auto {
-                        style.size.height = Val::Px(current_glyph_bounds.height());
-                    }
-                    continue 'text;
+                    (None, clicked_index)
                 }
-            }
-            commands.entity(entity).with_children(|parent| {
-                parent
-                    .spawn_bundle(cursor_style.0.clone())
-                    .insert(TextCursor)
-                    .insert(BlinkingTimer(Timer::new(cursor_interval.0, true)));
-            });
-        } else {
-            for (cursor, _, parent) in query_cursors.iter_mut() {
-                if parent.get() == entity {
-                    commands.entity(cursor).despawn_recursive();
+                Some(clicked_index) if count % 3 == 2 => {
+                    let (start, end) = word_bounds(&value, clicked_index);
+                    (Some(start), end)
                 }
+                Some(clicked_index) => {
+                    let (start, end) = line_bounds(&value, clicked_index);
+                    (Some(start), end)
+                }
+                None => (None, value.chars().count()),
+            };
+            focus.0 = Some(cursor);
+            if let Some(mut selection) = selection {
+                selection.anchor = anchor.filter(|&anchor| anchor != cursor);
             }
         }
     }
 }
 
-#[derive(Component, Clone, Copy, PartialEq, Eq)]
-pub struct CursorBlinkingInterval(pub Duration);
+/// Whether typing replaces the characters after the cursor instead of inserting before them.
+/// The Insert key toggles it, and the cursor is a [`CursorShape::Block`] while it's on.
+/// Characters are only replaced up to the end of the line. Default is false
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TextInputOvertype(pub bool);
 
-impl Default for CursorBlinkingInterval {
-    fn default() -> Self {
-        Self(Duration::from_millis(750))
-    }
+/// Byte index after the `count` characters that typing at `cursor` replaces in overtype mode.
+/// Line breaks are never replaced
+fn overtype_end(value: &str, cursor: usize, count: usize) -> usize {
+    value[cursor..]
+        .char_indices()
+        .take_while(|&(_, ch)| ch != '\n')
+        .take(count)
+        .last()
+        .map_or(cursor, |(index, ch)| cursor + index + ch.len_utf8())
 }
 
-#[derive(Component)]
-pub struct BlinkingTimer(pub Timer);
+/// What Escape does in a focused text input. Default is [`EscapeBehavior::Blur`]
+/// ```
+/// # use bevy_slimy_widgets::text_input::{EscapeBehavior, TextInputFocus, TextInputValue};
+/// let mut value = TextInputValue("hello".to_string());
+/// let mut focus = TextInputFocus(Some(5));
+///
+/// EscapeBehavior::Ignore.apply(&mut value, &mut focus, None);
+/// assert_eq!((value.0.as_str(), focus.0), ("hello", Some(5)));
+///
+/// EscapeBehavior::Blur.apply(&mut value, &mut focus, None);
+/// assert_eq!((value.0.as_str(), focus.0), ("hello", None));
+///
+/// let mut focus = TextInputFocus(Some(5));
+/// EscapeBehavior::ClearAndBlur.apply(&mut value, &mut focus, None);
+/// assert_eq!((value.0.as_str(), focus.0), ("", None));
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum EscapeBehavior {
+    /// Unfocus the text input
+    #[default]
+    Blur,
+    /// Clear the value and unfocus the text input. Read-only inputs are only unfocused
+    ClearAndBlur,
+    /// Keep the text input focused
+    Ignore,
+}
 
-pub fn text_input_blink_cursor_system(
-    time: Res<Time>,
-    mut query: Query<(&mut Visibility, &mut BlinkingTimer), With<TextCursor>>,
-) {
-    for (mut visibility, mut timer) in query.iter_mut() {
-        if timer.0.tick(time.delta()).just_finished() {
-            visibility.is_visible = !visibility.is_visible;
+impl EscapeBehavior {
+    /// Applies the behavior to a focused text input, as if Escape was pressed
+    pub fn apply(
+        self,
+        value: &mut TextInputValue,
+        focus: &mut TextInputFocus,
+        selection: Option<&mut TextInputSelection>,
+    ) {
+        if self == EscapeBehavior::Ignore {
+            return;
+        }
+        if self == EscapeBehavior::ClearAndBlur && !value.0.is_empty() {
+            value.0.clear();
+        }
+        focus.0 = None;
+        if let Some(selection) = selection {
+            selection.anchor = None;
         }
     }
 }
 
-#[derive(Component)]
-pub struct TextInputPlaceholder;
-#[derive(Component)]
-pub struct TextInputInner;
+/// Selects the whole value when the text input gains focus by a click, Tab or
+/// [`TextInputCommands::focus_text_input`], so typing replaces it.
+/// Requires [`TextInputSelection`]. Default is false
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SelectAllOnFocus(pub bool);
 
-pub fn text_input_create_system(
-    mut commands: Commands,
-    query: Query<
-        (
-            Entity,
-            &PlaceholderText,
-            &InputTextStyle,
-            &InputTextAlignment,
-            &TextInputValue,
-        ),
-        Added<PlaceholderText>,
-    >,
-) {
-    for (entity, placeholder, style, alignment, value) in query.iter() {
-        commands.entity(entity).with_children(|parent| {
-            parent
-                .spawn_bundle(TextBundle {
-                    style: Style {
-                        size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
-                        position_type: PositionType::Absolute,
-                        ..Default::default()
-                    },
-                    text: placeholder.0.clone(),
-                    visibility: Visibility {
-                        is_visible: value.is_empty(),
-                    },
-                    ..Default::default()
-                })
-                .insert(TextInputPlaceholder);
+/// Longest time between clicks for them to count as a double or triple click. Default is 400ms
+pub struct MultiClickInterval(pub Duration);
 
-            parent
-                .spawn_bundle(TextBundle {
-                    style: Style {
-                        size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
-                        position_type: PositionType::Absolute,
-                        ..Default::default()
-                    },
-                    text: Text::from_section(&value.0, style.0.clone()).with_alignment(alignment.0),
-                    ..Default::default()
-                })
-                .insert(TextInputInner);
-        });
+impl Default for MultiClickInterval {
+    fn default() -> Self {
+        Self(Duration::from_millis(400))
     }
 }
 
-pub fn text_input_update_system(
-    query: Query<(Entity, &TextInputValue), Changed<TextInputValue>>,
-    mut placeholder_query: Query<(&Parent, &mut Visibility), With<TextInputPlaceholder>>,
-    mut value_query: Query<(&Parent, &mut Text), With<TextInputInner>>,
-) {
-    for (entity, value) in query.iter() {
-        if let Some((_, mut placeholder_visibility)) = placeholder_query
-            .iter_mut()
-            .find(|(parent, _)| parent.get() == entity)
-        {
-            let mut inner_text = value_query
-                .iter_mut()
-                .find(|(parent, _)| parent.get() == entity)
-                .unwrap()
-                .1;
-            placeholder_visibility.is_visible = value.is_empty();
-            inner_text.sections[0].value = value.0.clone();
-            if inner_text.sections[0].value.ends_with('\n') {
-                inner_text.sections[0].value.push(' ');
-            }
+/// Added to a text input when it's clicked, to tell single, double and triple clicks apart.
+/// A double click selects a word, a triple click selects a line
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct TextInputClicks {
+    /// [`Time::seconds_since_startup`] of the last click
+    pub last_click: f64,
+    /// Number of clicks in a row, each within [`MultiClickInterval`] of the previous one
+    pub count: usize,
+}
+
+/// Character range of the word, whitespace or punctuation run around `index`.
+/// The character after `index` is used, or the one before it at the end of a line
+fn word_bounds(value: &str, index: usize) -> (usize, usize) {
+    fn class(c: char) -> u8 {
+        if c.is_whitespace() {
+            0
+        } else if c.is_alphanumeric() || c == '_' {
+            1
+        } else {
+            2
         }
     }
+
+    let chars = value.chars().collect::<Vec<_>>();
+    let index = index.min(chars.len());
+    // a line break is never a part of a word, so at the end of a line look at the character before
+    let index = if chars.get(index).is_none_or(|&c| c == '\n') {
+        match index.checked_sub(1) {
+            Some(before) if chars[before] != '\n' => before,
+            _ => return (index, index),
+        }
+    } else {
+        index
+    };
+    let class_at = class(chars[index]);
+    let same = |c: &char| *c != '\n' && class(*c) == class_at;
+    let start = index - chars[..index].iter().rev().take_while(|c| same(c)).count();
+    let end = index + chars[index..].iter().take_while(|c| same(c)).count();
+    (start, end)
 }
 
-pub fn text_input_system(
-    fonts: Res<Assets<Font>>,
-    mut query: Query<(
+/// Character range of the line around `index`, without the line break
+fn line_bounds(value: &str, index: usize) -> (usize, usize) {
+    let index = byte_index(value, index);
+    let start = value[..index].rfind('\n').map_or(0, |i| i + 1);
+    let end = value[index..].find('\n').map_or(value.len(), |i| index + i);
+    (char_index(value, start), char_index(value, end))
+}
+
+/// Tab moves the focus from this text input to the one with the next tab index,
+/// and Shift+Tab to the previous one, wrapping around at the ends.
+/// The order of inputs with the same index is unspecified, so give each input its own index
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TabIndex(pub i32);
+
+pub fn text_input_tab_focus_system(
+    keyboard: Res<Input<KeyCode>>,
+    mut query: Query<(
         Entity,
-        &InputTextStyle,
-        &mut TextInputValue,
+        &TabIndex,
         &mut TextInputFocus,
-        &TextInputConstrains,
+        &TextInputValue,
+        Option<&mut TextInputSelection>,
+        Option<&TextInputStatus>,
+        Option<&SelectAllOnFocus>,
     )>,
-    mut cursors: Query<(&Parent, &mut Visibility, &mut BlinkingTimer)>,
-    mut input: EventReader<KeyboardInput>,
-    mut char_evr: EventReader<ReceivedCharacter>,
 ) {
-    let keys = input
-        .iter()
-        .filter(|key| key.state == ButtonState::Pressed)
-        .filter_map(|key| key.key_code)
-        .collect::<Vec<_>>();
-    let chars_all = char_evr.iter().map(|rc| rc.char).collect::<Vec<_>>();
-    let s = chars_all
-        .iter()
-        .copied()
-        .filter(|ch| !ch.is_control())
-        .collect::<String>();
-    let control_chars = chars_all
+    if !keyboard.just_pressed(KeyCode::Tab) {
+        return;
+    }
+    let backwards = keyboard.any_pressed([KeyCode::LShift, KeyCode::RShift]);
+    let mut order = query
         .iter()
-        .copied()
-        .filter(|ch| ch.is_control())
+        .filter(|(.., status, _)| *status != Some(&TextInputStatus::Disabled))
+        .map(|(entity, tab_index, focus, ..)| (*tab_index, entity, focus.0.is_some()))
         .collect::<Vec<_>>();
-    for (entity, style, mut value, mut focus, constrains) in query.iter_mut() {
-        if let Some(cursor) = focus.0.as_mut() {
-            let font = fonts.get(&style.0.font).unwrap().font.clone();
-            let mut new_value = value.0.clone();
-            let mut new_cursor = *cursor;
-            if control_chars.contains(&'\r') {
-                // new line
-                new_value.insert(new_cursor, '\n');
-                new_cursor += 1;
-            }
-            if control_chars.contains(&'\u{1}') {
-                // Ctrl-A
-                new_cursor = 0;
+    order.sort_by_key(|&(tab_index, entity, _)| (tab_index, entity));
+    let current = match order.iter().position(|&(.., focused)| focused) {
+        Some(current) => current,
+        None => return,
+    };
+    let next = if backwards {
+        (current + order.len() - 1) % order.len()
+    } else {
+        (current + 1) % order.len()
+    };
+    let (current, next) = (order[current].1, order[next].1);
+    if current == next {
+        return;
+    }
+    for (entity, _, mut focus, value, selection, _, select_all) in query.iter_mut() {
+        let mut anchor = None;
+        if entity == current || (entity != next && focus.0.is_some()) {
+            focus.0 = None;
+        } else if entity == next {
+            let cursor = value.chars().count();
+            focus.0 = Some(cursor);
+            if select_all.is_some_and(|select| select.0) && cursor > 0 {
+                anchor = Some(0);
             }
-            if control_chars.contains(&'\u{5}') {
-                // Ctrl-E
-                new_cursor = new_value.len();
+        } else {
+            continue;
+        }
+        if let Some(mut selection) = selection {
+            if selection.anchor != anchor {
+                selection.anchor = anchor;
             }
-            if control_chars.contains(&'\u{8}') && new_cursor != 0 {
-                // backspace
-                new_value.remove(new_cursor - 1);
-                new_cursor -= 1;
-            }
-            if control_chars.contains(&'\u{7f}') && new_cursor < new_value.len() {
-                // delete
-                new_value.remove(new_cursor);
-            }
-            if control_chars.contains(&'\u{16}') {
-                // paste
-                if let Ok(mut clipboard) = ClipboardContext::new() {
-                    if let Ok(contents) = clipboard.get_contents() {
-                        new_value.insert_str(new_cursor, &contents);
-                        new_cursor += contents.len();
-                    }
+        }
+    }
+}
+
+/// Pointer position relative to the top left corner of a text node
+fn local_text_position(cursor_position: Vec2, node: &Node, transform: &GlobalTransform) -> Vec2 {
+    // UI y axis points up, but the text is laid out from its top left corner
+    let center = transform.translation().truncate();
+    Vec2::new(
+        cursor_position.x - (center.x - node.size.x / 2.0),
+        center.y + node.size.y / 2.0 - cursor_position.y,
+    )
+}
+
+/// Added to a text input while the left mouse button is held after clicking it.
+/// Dragging selects the text between [`TextInputDrag::start`] and [`TextInputDrag::current`]
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TextInputDrag {
+    /// Character index where the button was pressed
+    pub start: usize,
+    /// Character index under the pointer
+    pub current: usize,
+}
+
+pub fn text_input_drag_system(
+    mut commands: Commands,
+    mouse: Res<Input<MouseButton>>,
+    fonts: Res<Assets<Font>>,
+    mut glyph_calculators: ResMut<GlyphCalculatorCache>,
+    windows: Res<Windows>,
+    mut query: Query<(
+        Entity,
+        &mut TextInputDrag,
+        &mut TextInputFocus,
+        &mut TextInputSelection,
+        &TextInputValue,
+        &InputTextStyle,
+        Option<&FallbackFonts>,
+        Option<&TextInputMask>,
+    )>,
+    query_text: Query<(&Parent, &Text, &Node, &GlobalTransform), With<TextInputInner>>,
+) {
+    let window = windows.get_primary();
+    let scale_factor = window.map_or(1.0, |window| window.scale_factor() as f32);
+    let cursor_position = window.and_then(|window| window.cursor_position());
+    for (entity, mut drag, mut focus, mut selection, value, text_style, fallback_fonts, mask) in
+        query.iter_mut()
+    {
+        if !mouse.pressed(MouseButton::Left) || focus.0.is_none() {
+            commands.entity(entity).remove::<TextInputDrag>();
+            continue;
+        }
+        let cursor_position = match cursor_position {
+            Some(cursor_position) => cursor_position,
+            None => continue,
+        };
+        let (_, text, node, transform) = match query_text
+            .iter()
+            .find(|(parent, ..)| parent.get() == entity)
+        {
+            Some(text) => text,
+            None => continue,
+        };
+        let calculator = match glyph_calculators.get(&fonts, &text_style.0.font, fallback_fonts) {
+            Some(calculator) => calculator,
+            None => continue,
+        };
+        let physical_scale = PxScale::from(text.sections[0].style.font_size * scale_factor);
+        // cursor_index_at clamps to the value, so dragging past the end selects up to the end
+        let current = cursor_index_at(
+            &masked(&value.0, mask),
+            calculator,
+            physical_scale,
+            &text.alignment,
+            node.size * scale_factor,
+            local_text_position(cursor_position, node, transform) * scale_factor,
+        );
+        if drag.current == current {
+            continue;
+        }
+        drag.current = current;
+        focus.0 = Some(current);
+        selection.anchor = Some(drag.start).filter(|&start| start != current);
+    }
+}
+
+/// Focuses and unfocuses text inputs from code
+/// ```
+/// # use bevy::ecs::system::CommandQueue;
+/// # use bevy::prelude::*;
+/// # use bevy_slimy_widgets::text_input::{
+/// #     SelectAllOnFocus, TextInputCommands, TextInputFocus, TextInputSelection, TextInputValue,
+/// # };
+/// let mut world = World::new();
+/// let name = world
+///     .spawn()
+///     .insert(TextInputValue("Slime".to_string()))
+///     .insert(TextInputFocus(None))
+///     .insert(TextInputSelection::default())
+///     .insert(SelectAllOnFocus(true))
+///     .id();
+/// let email = world
+///     .spawn()
+///     .insert(TextInputValue::default())
+///     .insert(TextInputFocus(Some(0)))
+///     .id();
+///
+/// let mut queue = CommandQueue::default();
+/// Commands::new(&mut queue, &world).focus_text_input(name);
+/// queue.apply(&mut world);
+/// // the cursor goes to the end, the value is selected and the other input loses focus
+/// assert_eq!(world.get::<TextInputFocus>(name).unwrap().0, Some(5));
+/// assert_eq!(world.get::<TextInputSelection>(name).unwrap().anchor, Some(0));
+/// assert_eq!(world.get::<TextInputFocus>(email).unwrap().0, None);
+///
+/// Commands::new(&mut queue, &world).blur_text_input(name);
+/// queue.apply(&mut world);
+/// assert_eq!(world.get::<TextInputFocus>(name).unwrap().0, None);
+/// ```
+pub trait TextInputCommands {
+    /// Focuses the text input with the cursor at the end of the value, and unfocuses the others.
+    /// Does nothing if the input is [`TextInputStatus::Disabled`]
+    fn focus_text_input(&mut self, entity: Entity);
+    /// Unfocuses the text input
+    fn blur_text_input(&mut self, entity: Entity);
+    /// Empties the value, forgets its [`TextInputHistory`] and unfocuses the text input
+    /// ```
+    /// # use bevy::ecs::system::CommandQueue;
+    /// # use bevy::prelude::*;
+    /// # use bevy_slimy_widgets::text_input::{TextInputCommands, TextInputFocus, TextInputValue};
+    /// let mut world = World::new();
+    /// let entity = world
+    ///     .spawn()
+    ///     .insert(TextInputValue("hello".to_string()))
+    ///     .insert(TextInputFocus(Some(5)))
+    ///     .id();
+    /// let mut queue = CommandQueue::default();
+    /// Commands::new(&mut queue, &world).clear_text_input(entity);
+    /// queue.apply(&mut world);
+    /// assert_eq!(world.get::<TextInputValue>(entity).unwrap().0, "");
+    /// assert_eq!(world.get::<TextInputFocus>(entity).unwrap().0, None);
+    /// ```
+    fn clear_text_input(&mut self, entity: Entity);
+}
+
+impl TextInputCommands for Commands<'_, '_> {
+    fn focus_text_input(&mut self, entity: Entity) {
+        self.add(FocusTextInput(entity));
+    }
+
+    fn blur_text_input(&mut self, entity: Entity) {
+        self.add(BlurTextInput(entity));
+    }
+
+    fn clear_text_input(&mut self, entity: Entity) {
+        self.add(ClearTextInput(entity));
+    }
+}
+
+struct FocusTextInput(Entity);
+
+impl Command for FocusTextInput {
+    fn write(self, world: &mut World) {
+        let (cursor, select_all) = match world.get_entity(self.0) {
+            Some(entity) if entity.get::<TextInputStatus>() != Some(&TextInputStatus::Disabled) => {
+                match entity.get::<TextInputValue>() {
+                    Some(value) => (
+                        value.chars().count(),
+                        entity
+                            .get::<SelectAllOnFocus>()
+                            .is_some_and(|select| select.0),
+                    ),
+                    None => return,
                 }
             }
-            if keys.contains(&KeyCode::Left) && new_cursor > 0 {
-                new_cursor -= 1;
-            }
-            if keys.contains(&KeyCode::Right) && new_cursor < new_value.len() {
-                new_cursor += 1;
+            _ => return,
+        };
+        for (entity, mut focus, selection) in world
+            .query::<(Entity, &mut TextInputFocus, Option<&mut TextInputSelection>)>()
+            .iter_mut(world)
+        {
+            let new_focus = if entity == self.0 { Some(cursor) } else { None };
+            if focus.0 != new_focus {
+                let gained = focus.0.is_none();
+                focus.0 = new_focus;
+                if let Some(mut selection) = selection {
+                    selection.anchor = Some(0).filter(|_| gained && select_all && cursor > 0);
+                }
             }
+        }
+    }
+}
 
-            if keys.contains(&KeyCode::Home) {
-                new_cursor -= new_value[..new_cursor]
-                    .chars()
-                    .rev()
-                    .position(|ch| ch == '\n')
-                    .unwrap_or(new_cursor);
+struct BlurTextInput(Entity);
+
+impl Command for BlurTextInput {
+    fn write(self, world: &mut World) {
+        if let Some(mut focus) = world.get_mut::<TextInputFocus>(self.0) {
+            if focus.0.is_some() {
+                focus.0 = None;
             }
-            if keys.contains(&KeyCode::End) {
-                new_cursor += new_value[new_cursor..]
-                    .chars()
-                    .position(|ch| ch == '\n')
-                    .unwrap_or(new_value.len() - new_cursor);
+        }
+        if let Some(mut selection) = world.get_mut::<TextInputSelection>(self.0) {
+            if selection.anchor.is_some() {
+                selection.anchor = None;
             }
+        }
+    }
+}
 
-            let scale = PxScale {
-                x: style.0.font_size,
-                y: style.0.font_size,
-            };
-            if keys.contains(&KeyCode::Up) {
-                if new_value[..new_cursor].split('\n').count() <= 1 {
-                    new_cursor = 0;
-                } else {
-                    let mut lines_before_cursor = new_value[..new_cursor].split('\n').rev();
-                    let current_line_before_cursor = lines_before_cursor.next().unwrap();
-                    let previous_line = lines_before_cursor.next().unwrap();
-                    let target_width = text_width(current_line_before_cursor, font.clone(), scale);
-                    let x = (0..=previous_line.len())
-                        .map(|i| (i, text_width(&previous_line[..i], font.clone(), scale)))
-                        .min_by(|(_, width1), (_, width2)| {
-                            // width is never f32::NAN, so unwrap is safe
-                            (width1 - target_width)
-                                .abs()
-                                .partial_cmp(&(width2 - target_width).abs())
-                                .unwrap()
-                        })
-                        .unwrap()
-                        .0;
-                    new_cursor = x + lines_before_cursor
-                        .map(|line| line.len() + 1)
-                        .sum::<usize>();
-                }
-            }
-            if keys.contains(&KeyCode::Down) {
-                if new_value[new_cursor..].split('\n').count() <= 1 {
-                    new_cursor = new_value.len();
-                } else {
-                    let mut lines_after_cursor = new_value[new_cursor..].split('\n');
-                    let current_line_before_cursor =
-                        new_value[..new_cursor].split('\n').last().unwrap();
-                    let current_line_after_cursor = lines_after_cursor.next().unwrap();
-                    let next_line = lines_after_cursor.next().unwrap();
-                    let target_width = text_width(current_line_before_cursor, font.clone(), scale);
-                    let x = (0..=next_line.len())
-                        .map(|i| (i, text_width(&next_line[..i], font.clone(), scale)))
-                        .min_by(|(_, width1), (_, width2)| {
-                            // width is never f32::NAN, so unwrap is safe
-                            (width1 - target_width)
-                                .abs()
-                                .partial_cmp(&(width2 - target_width).abs())
-                                .unwrap()
-                        })
-                        .unwrap()
-                        .0;
-                    new_cursor = new_cursor + current_line_after_cursor.len() + 1 + x;
-                }
+struct ClearTextInput(Entity);
+
+impl Command for ClearTextInput {
+    fn write(self, world: &mut World) {
+        if let Some(mut value) = world.get_mut::<TextInputValue>(self.0) {
+            if !value.0.is_empty() {
+                value.0.clear();
             }
+        }
+        if let Some(mut history) = world.get_mut::<TextInputHistory>(self.0) {
+            history.clear();
+        }
+        BlurTextInput(self.0).write(world);
+    }
+}
 
-            new_value.insert_str(new_cursor, &s);
-            new_cursor += s.len();
+/// Extends the area that focuses the text input on click beyond its node, e.g. for thin inputs
+/// or touch screens. Without it, only the node itself is clickable
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct ClickPadding(pub UiRect<Val>);
 
-            if value.0 != new_value || *cursor != new_cursor {
-                if value.0 != new_value && !constrains.test(&value.0, &new_value) {
-                    continue;
-                }
-                value.0 = new_value;
-                *cursor = new_cursor;
-                for (parent, mut visibility, mut timer) in cursors.iter_mut() {
-                    if parent.get() == entity {
-                        visibility.is_visible = true;
-                        timer.0.reset();
-                        break;
+/// Invisible node spawned for [`ClickPadding`] that covers the input and its padding
+#[derive(Component)]
+pub struct TextInputHitArea;
+
+pub fn text_input_click_padding_create_system(
+    mut commands: Commands,
+    query: Query<(Entity, &ClickPadding), Added<ClickPadding>>,
+) {
+    for (entity, click_padding) in query.iter() {
+        commands.entity(entity).with_children(|parent| {
+            parent
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        position: UiRect {
+                            left: negate_val(click_padding.0.left),
+                            right: negate_val(click_padding.0.right),
+                            top: negate_val(click_padding.0.top),
+                            bottom: negate_val(click_padding.0.bottom),
+                        },
+                        position_type: PositionType::Absolute,
+                        ..Default::default()
+                    },
+                    color: Color::NONE.into(),
+                    ..Default::default()
+                })
+                .insert(Interaction::default())
+                .insert(FocusPolicy::Pass)
+                .insert(TextInputHitArea);
+        });
+    }
+}
+
+pub fn text_input_click_padding_system(
+    hit_areas: Query<(&Parent, &Interaction), (With<TextInputHitArea>, Changed<Interaction>)>,
+    mut query: Query<(
+        &mut TextInputFocus,
+        &TextInputValue,
+        Option<&TextInputStatus>,
+        Option<&mut TextInputSelection>,
+        Option<&SelectAllOnFocus>,
+    )>,
+) {
+    for (parent, interaction) in hit_areas.iter() {
+        if *interaction == Interaction::Clicked {
+            if let Ok((mut focus, value, status, selection, select_all)) =
+                query.get_mut(parent.get())
+            {
+                if focus.0.is_none() && status != Some(&TextInputStatus::Disabled) {
+                    let cursor = value.chars().count();
+                    focus.0 = Some(cursor);
+                    if let Some(mut selection) = selection {
+                        if select_all.is_some_and(|select| select.0) && cursor > 0 {
+                            selection.anchor = Some(0);
+                        }
                     }
                 }
             }
@@ -512,52 +1299,4049 @@ pub fn text_input_system(
     }
 }
 
-pub fn text_input_font_decrease_system(
+fn negate_val(val: Val) -> Val {
+    match val {
+        Val::Px(px) => Val::Px(-px),
+        Val::Percent(percent) => Val::Percent(-percent),
+        Val::Undefined | Val::Auto => Val::Px(0.0),
+    }
+}
+
+pub fn text_input_focus_style_system(
+    mut query: Query<
+        (
+            &TextInputFocus,
+            &FocusStyle,
+            &mut UiColor,
+            Option<&TextInputStatus>,
+        ),
+        Changed<TextInputFocus>,
+    >,
+) {
+    for (focus, focus_style, mut color, status) in query.iter_mut() {
+        // disabled inputs keep their DisabledColor
+        if status == Some(&TextInputStatus::Disabled) {
+            continue;
+        }
+        *color = if focus.0.is_some() {
+            focus_style.focused
+        } else {
+            focus_style.unfocused
+        };
+    }
+}
+
+pub fn text_input_move_cursor_system(
+    mut commands: Commands,
     fonts: Res<Assets<Font>>,
-    query: Query<(
-        Entity,
-        &InputTextStyle,
-        &TextInputTargetSize,
-        &TextInputValue,
-    )>,
-    mut text: Query<(&Parent, &mut Text), With<TextInputInner>>,
+    mut glyph_calculators: ResMut<GlyphCalculatorCache>,
+    query: Query<
+        (
+            Entity,
+            &TextInputFocus,
+            &TextInputValue,
+            &TextCursorStyle,
+            &CursorBlinkingInterval,
+            Option<&InputAdornment>,
+            &InputTextStyle,
+            Option<&FallbackFonts>,
+            Option<&CursorBlink>,
+            Option<&TextInputMask>,
+            Option<&TextInputScroll>,
+            Option<&CursorShape>,
+            Option<&CursorMovementAnimation>,
+            Option<&TextInputOvertype>,
+        ),
+        Or<(
+            Changed<TextInputFocus>,
+            Changed<TextInputScroll>,
+            Changed<CursorShape>,
+            Changed<TextInputOvertype>,
+            Changed<InputAdornment>,
+        )>,
+    >,
+    query_text: Query<(&Parent, &Text), With<TextInputInner>>,
+    mut query_cursors: Query<(Entity, &mut Style, &Parent), With<TextCursor>>,
+    windows: Res<Windows>,
 ) {
-    for (entity, style, target_size, value) in query.iter() {
-        if let Some(target_size) = target_size.0 {
-            for mut text in text
-                .iter_mut()
-                .filter(|(parent, _)| parent.get() == entity)
-                .map(|(_, text)| text)
-            {
-                let font = fonts
-                    .get(&text.sections[0].style.font)
-                    .unwrap()
-                    .font
-                    .clone();
-                let scale = PxScale {
-                    x: style.0.font_size,
-                    y: style.0.font_size,
-                };
+    let scale_factor = windows
+        .get_primary()
+        .map_or(1.0, |window| window.scale_factor() as f32);
+    'text: for (
+        entity,
+        focus,
+        value,
+        cursor_style,
+        cursor_interval,
+        adornment,
+        text_style,
+        fallback_fonts,
+        cursor_blink,
+        mask,
+        scroll,
+        shape,
+        movement_animation,
+        overtype,
+    ) in query.iter()
+    {
+        let value = masked(&value.0, mask);
+        if let Some(char_index) = focus.0 {
+            for (cursor, mut style, parent) in query_cursors.iter_mut() {
+                if parent.get() == entity {
+                    let previous = style.position;
+                    let text = query_text
+                        .iter()
+                        .find(|(parent, _)| parent.get() == entity)
+                        .unwrap()
+                        .1;
+                    let calculator =
+                        match glyph_calculators.get(&fonts, &text_style.0.font, fallback_fonts) {
+                            Some(calculator) => calculator,
+                            None => continue 'text,
+                        };
+                    let font = calculator.fonts()[0].clone();
 
-                let width = text_width(&value.0, font.clone(), scale);
-                let height = value.0.lines().count() as f32 * font.as_scaled(scale).height();
+                    let text_before_cursor = &value[..byte_index(&value, char_index)];
+                    let font_size = text.sections[0].style.font_size;
+                    let scale = PxScale {
+                        x: font_size,
+                        y: font_size,
+                    };
+                    // bevy lays text out in physical pixels, so measure the same way
+                    // to avoid rounding drift between the cursor and the rendered glyphs
+                    let physical_scale = PxScale::from(font_size * scale_factor);
+                    let x = text_width_with_fallback(
+                        text_before_cursor.split('\n').next_back().unwrap(),
+                        calculator,
+                        physical_scale,
+                    ) / scale_factor;
+                    let anchor = cursor_anchor(&cursor_style.0.style);
+                    // the text node is shifted by the scroll offset, whatever its alignment
+                    let y = line_top(
+                        &value,
+                        text_before_cursor.len(),
+                        anchor,
+                        line_height(&font, physical_scale) / scale_factor,
+                        scroll.map_or(0.0, |scroll| scroll.offset),
+                    );
+                    let x = x + adornment.map_or(0.0, |adornment| adornment.prefix_width);
+                    // snap to physical pixels so the cursor doesn't shimmer. x is rounded up
+                    // so the cursor never overlaps the glyph before it
+                    style.position.left = Val::Px((x * scale_factor).ceil() / scale_factor);
+                    style.position.top = Val::Px((y * scale_factor).round() / scale_factor);
 
-                let width_factor = width / target_size.width;
-                let height_factor = height / target_size.height;
-                let factor = width_factor.max(height_factor);
-                if factor > 1.0 {
-                    text.sections[0].style.font_size = style.0.font_size / factor;
+                    let current_glyph_bounds = font.glyph_bounds(&Glyph {
+                        id: font.glyph_id(value.chars().nth(char_index).unwrap_or(' ')),
+                        scale,
+                        position: Default::default(),
+                    });
+                    if cursor_style.0.style.size.width == Val::Auto {
+                        style.size.width = Val::Px(current_glyph_bounds.width());
+                    }
+                    if cursor_style.0.style.size.height == Val::Auto {
+                        style.size.height = Val::Px(current_glyph_bounds.height());
+                    }
+                    let thickness = font_size / 12.0;
+                    let shape = match overtype {
+                        Some(TextInputOvertype(true)) => Some(&CursorShape::Block),
+                        _ => shape,
+                    };
+                    match shape {
+                        None => {}
+                        Some(CursorShape::Bar) => {
+                            style.size = Size::new(Val::Px(thickness), Val::Px(font_size));
+                        }
+                        Some(CursorShape::Block) => {
+                            style.size = Size::new(
+                                Val::Px(current_glyph_bounds.width()),
+                                Val::Px(font_size),
+                            );
+                        }
+                        Some(CursorShape::Underline) => {
+                            style.size = Size::new(
+                                Val::Px(current_glyph_bounds.width()),
+                                Val::Px(thickness),
+                            );
+                            // a bar spans the whole line, so move the thinner node from where
+                            // the margins of TextCursorStyle put it down to the baseline
+                            let baseline =
+                                font.as_scaled(scale).ascent() - (font_size - thickness) * anchor;
+                            style.position.top =
+                                Val::Px(((baseline + y) * scale_factor).round() / scale_factor);
+                        }
+                    }
+                    if movement_animation.is_some() {
+                        // text_input_cursor_glide_system moves the cursor to the new position
+                        let to = px_position(&style.position);
+                        style.position = previous;
+                        commands.entity(cursor).insert(CursorGlide {
+                            from: Vec2::new(px_or(previous.left, to.x), px_or(previous.top, to.y)),
+                            to,
+                            elapsed: Duration::ZERO,
+                        });
+                    }
+                    continue 'text;
+                }
+            }
+            let blinking_period = match cursor_blink {
+                Some(CursorBlink::Smooth) => cursor_interval.0 * 2,
+                _ => cursor_interval.0,
+            };
+            commands.entity(entity).with_children(|parent| {
+                parent
+                    .spawn_bundle(cursor_style.0.clone())
+                    .insert(TextCursor)
+                    .insert(BlinkingTimer(Timer::new(blinking_period, true)));
+            });
+        } else {
+            for (cursor, _, parent) in query_cursors.iter_mut() {
+                if parent.get() == entity {
+                    commands.entity(cursor).despawn_recursive();
                 }
             }
         }
     }
 }
 
-fn text_width(text: &str, font: FontArc, scale: PxScale) -> f32 {
-    GlyphCalculatorBuilder::using_font(font)
-        .build()
-        .cache_scope()
-        .glyph_bounds(Section::default().add_text(glyph_brush::Text::new(text).with_scale(scale)))
-        .map(|rect| rect.width())
-        .unwrap_or_default()
+/// Where the margins of [`TextCursorStyle`] put the cursor node: 0.0 at the top, 0.5 centered,
+/// 1.0 at the bottom. Without a top margin, the node sits at the bottom like in flexbox
+fn cursor_anchor(cursor_style: &Style) -> f32 {
+    match (cursor_style.margin.top, cursor_style.margin.bottom) {
+        (Val::Auto, Val::Auto) => 0.5,
+        (Val::Px(_) | Val::Percent(_), Val::Undefined) => 0.0,
+        _ => 1.0,
+    }
+}
+
+/// How many lines below its unpositioned place the cursor at `cursor` (a byte index) is.
+/// The cursor node is aligned to the text like the lines are, so a top-aligned cursor
+/// starts on the first line, and a bottom-aligned one on the last line. A trailing `\n`
+/// starts an empty last line, the same as the space that is displayed after it
+fn cursor_line_offset(value: &str, cursor: usize, anchor: f32) -> f32 {
+    let line = value[..cursor].matches('\n').count();
+    let last_line = value.matches('\n').count();
+    line as f32 - last_line as f32 * anchor
+}
+
+/// Where [`TextAlignment::vertical`] puts the lines, the same way as [`cursor_anchor`]
+fn text_anchor(alignment: &TextAlignment) -> f32 {
+    match alignment.vertical {
+        VerticalAlign::Top => 0.0,
+        VerticalAlign::Center => 0.5,
+        VerticalAlign::Bottom => 1.0,
+    }
+}
+
+/// Where [`TextAlignment::horizontal`] puts each line, 0.0 at the left and 1.0 at the right
+fn text_horizontal_anchor(alignment: &TextAlignment) -> f32 {
+    match alignment.horizontal {
+        HorizontalAlign::Left => 0.0,
+        HorizontalAlign::Center => 0.5,
+        HorizontalAlign::Right => 1.0,
+    }
+}
+
+/// Distance from the left of a view that is `visible_width` wide to a line that is
+/// `line_width` wide, for text aligned at `anchor`
+fn line_left_in_view(line_width: f32, anchor: f32, visible_width: f32) -> f32 {
+    (visible_width - line_width) * anchor
+}
+
+/// Vertical distance from the unpositioned place of a line-high node aligned at `anchor`
+/// to the line with `cursor` (a byte index), after the text is scrolled down by `scroll_offset`
+fn line_top(value: &str, cursor: usize, anchor: f32, line_height: f32, scroll_offset: f32) -> f32 {
+    line_height * cursor_line_offset(value, cursor, anchor) + scroll_offset
+}
+
+/// Distance from the top of a view that is `visible_height` high to the line with `cursor`,
+/// for text aligned at `anchor` and scrolled down by `scroll_offset`
+fn line_top_in_view(
+    value: &str,
+    cursor: usize,
+    anchor: f32,
+    line_height: f32,
+    visible_height: f32,
+    scroll_offset: f32,
+) -> f32 {
+    // an unpositioned line aligned like the text starts this far from the top of the view
+    (visible_height - line_height) * anchor
+        + line_top(value, cursor, anchor, line_height, scroll_offset)
+}
+
+/// The range of [`TextInputScroll::offset`] in which text aligned at `anchor` covers the view.
+/// Text that fits can't be scrolled
+fn scroll_range(text_height: f32, visible_height: f32, anchor: f32) -> (f32, f32) {
+    let overflow = (text_height - visible_height).max(0.0);
+    (-overflow * (1.0 - anchor), overflow * anchor)
+}
+
+/// The offset closest to `offset` that shows the whole line starting at `top`, which is
+/// relative to the top of the view and already includes `offset`
+fn scroll_into_view(offset: f32, top: f32, line_height: f32, visible_height: f32) -> f32 {
+    if top < 0.0 {
+        offset - top
+    } else if top + line_height > visible_height {
+        offset - (top + line_height - visible_height)
+    } else {
+        offset
+    }
+}
+
+fn px_position(position: &UiRect<Val>) -> Vec2 {
+    Vec2::new(px_or(position.left, 0.0), px_or(position.top, 0.0))
+}
+
+fn px_or(val: Val, default: f32) -> f32 {
+    match val {
+        Val::Px(px) => px,
+        _ => default,
+    }
+}
+
+pub fn text_input_cursor_glide_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &Parent, &mut Style, &mut CursorGlide), With<TextCursor>>,
+    inputs: Query<&CursorMovementAnimation>,
+) {
+    for (cursor, parent, mut style, mut glide) in query.iter_mut() {
+        glide.elapsed += time.delta();
+        let t = match inputs.get(parent.get()) {
+            Ok(animation) if !animation.duration.is_zero() => {
+                glide.elapsed.as_secs_f32() / animation.duration.as_secs_f32()
+            }
+            _ => 1.0,
+        };
+        let position = glide.from.lerp(glide.to, Easing::EaseOut.apply(t));
+        style.position.left = Val::Px(position.x);
+        style.position.top = Val::Px(position.y);
+        if t >= 1.0 {
+            commands.entity(cursor).remove::<CursorGlide>();
+        }
+    }
+}
+
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub struct CursorBlinkingInterval(pub Duration);
+
+impl Default for CursorBlinkingInterval {
+    fn default() -> Self {
+        Self(Duration::from_millis(750))
+    }
+}
+
+#[derive(Component)]
+pub struct BlinkingTimer(pub Timer);
+
+/// A highlight of one selected line, spawned as a child of the text input before
+/// [`TextInputInner`] so it's drawn behind the text. It doesn't depend on the [`TextCursor`],
+/// which is only spawned after the input is focused
+#[derive(Component)]
+pub struct TextSelectionHighlight;
+
+pub fn text_input_selection_highlight_system(
+    mut commands: Commands,
+    fonts: Res<Assets<Font>>,
+    mut glyph_calculators: ResMut<GlyphCalculatorCache>,
+    query: Query<
+        (
+            Entity,
+            &TextInputFocus,
+            &TextInputSelection,
+            &TextInputValue,
+            &InputTextStyle,
+            Option<&FallbackFonts>,
+            Option<&SelectionColor>,
+            Option<&TextInputMask>,
+            &Node,
+            &Style,
+            Option<&InputAdornment>,
+            Option<&TextInputScroll>,
+        ),
+        Or<(
+            Changed<TextInputFocus>,
+            Changed<TextInputSelection>,
+            Changed<TextInputScroll>,
+        )>,
+    >,
+    query_text: Query<(&Parent, &Text), With<TextInputInner>>,
+    highlights: Query<(Entity, &Parent), With<TextSelectionHighlight>>,
+    children: Query<&Children>,
+    windows: Res<Windows>,
+) {
+    let scale_factor = windows
+        .get_primary()
+        .map_or(1.0, |window| window.scale_factor() as f32);
+    for (
+        entity,
+        focus,
+        selection,
+        value,
+        text_style,
+        fallback_fonts,
+        color,
+        mask,
+        node,
+        node_style,
+        adornment,
+        scroll,
+    ) in query.iter()
+    {
+        let value = masked(&value.0, mask);
+        for (highlight, parent) in highlights.iter() {
+            if parent.get() == entity {
+                commands.entity(highlight).despawn_recursive();
+            }
+        }
+        let range = match focus.0.and_then(|cursor| selection.range(cursor)) {
+            Some(range) => range,
+            None => continue,
+        };
+        let calculator = match glyph_calculators.get(&fonts, &text_style.0.font, fallback_fonts) {
+            Some(calculator) => calculator,
+            None => continue,
+        };
+        let text = match query_text.iter().find(|(parent, _)| parent.get() == entity) {
+            Some((_, text)) => text,
+            None => continue,
+        };
+        // measured the same way as in text_input_move_cursor_system
+        let physical_scale = PxScale::from(text.sections[0].style.font_size * scale_factor);
+        let width =
+            |text: &str| text_width_with_fallback(text, calculator, physical_scale) / scale_factor;
+        let line_height = line_height(&calculator.fonts()[0], physical_scale) / scale_factor;
+        let start = byte_index(&value, range.start);
+        let end = byte_index(&value, range.end);
+        let color = color.copied().unwrap_or_default().0;
+
+        // the highlights are placed in the same box as the text node, see inner_text_position
+        let inset = inner_text_position(node_style);
+        let px = |val: Val| match val {
+            Val::Px(px) => px,
+            _ => 0.0,
+        };
+        let visible_width = node.size.x - px(inset.left) - px(inset.right);
+        let visible_height = node.size.y - px(inset.top) - px(inset.bottom);
+        let anchor = text_anchor(&text.alignment);
+        let horizontal_anchor = text_horizontal_anchor(&text.alignment);
+        let prefix_width = adornment.map_or(0.0, |adornment| adornment.prefix_width);
+        let scroll_offset = scroll.map_or(0.0, |scroll| scroll.offset);
+
+        let mut line_start = 0;
+        let mut spawned = Vec::new();
+        for line in value.split('\n') {
+            let line_end = line_start + line.len();
+            if line_end >= start && line_start <= end {
+                let from = start.max(line_start) - line_start;
+                let to = end.min(line_end) - line_start;
+                let line_left = line_left_in_view(width(line), horizontal_anchor, visible_width);
+                let left = line_left + width(&line[..from]);
+                let right = line_left + width(&line[..to]);
+                let top = line_top_in_view(
+                    &value,
+                    line_start,
+                    anchor,
+                    line_height,
+                    visible_height,
+                    scroll_offset,
+                );
+                let highlight = commands
+                    .spawn_bundle(NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            position: UiRect {
+                                left: add_vals(inset.left, Val::Px(prefix_width + left)),
+                                top: add_vals(inset.top, Val::Px(top)),
+                                ..Default::default()
+                            },
+                            size: Size::new(Val::Px(right - left), Val::Px(line_height)),
+                            ..Default::default()
+                        },
+                        color,
+                        ..Default::default()
+                    })
+                    .insert(TextSelectionHighlight)
+                    .id();
+                spawned.push(highlight);
+            }
+            line_start = line_end + 1;
+        }
+        // UI nodes are drawn in the order of the children, so this puts them behind the text.
+        // The old highlights are despawned before the new ones are inserted
+        let index = children.get(entity).map_or(0, |children| {
+            children
+                .iter()
+                .filter(|&&child| highlights.get(child).is_err())
+                .position(|&child| query_text.get(child).is_ok())
+                .unwrap_or(0)
+        });
+        commands.entity(entity).insert_children(index, &spawned);
+    }
+}
+
+/// How the cursor blinks
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CursorBlink {
+    /// The cursor is shown and hidden every [`CursorBlinkingInterval`]
+    #[default]
+    Hard,
+    /// The cursor fades out and back in, taking [`CursorBlinkingInterval`] each way
+    Smooth,
+}
+
+pub fn text_input_blink_cursor_system(
+    time: Res<Time>,
+    mut query: Query<
+        (&Parent, &mut Visibility, &mut BlinkingTimer, &mut UiColor),
+        With<TextCursor>,
+    >,
+    inputs: Query<(&TextCursorStyle, Option<&CursorBlink>)>,
+) {
+    for (parent, mut visibility, mut timer, mut color) in query.iter_mut() {
+        timer.0.tick(time.delta());
+        match inputs.get(parent.get()) {
+            Ok((cursor_style, Some(CursorBlink::Smooth))) => {
+                // the timer spans a whole fade out and in, see text_input_move_cursor_system
+                let mut faded = cursor_style.0.color.0;
+                faded.set_a(faded.a() * (1.0 - 2.0 * timer.0.percent()).abs());
+                color.0 = faded;
+            }
+            _ => {
+                if timer.0.just_finished() {
+                    visibility.is_visible = !visibility.is_visible;
+                }
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct TextInputPlaceholder;
+#[derive(Component)]
+pub struct TextInputInner;
+
+pub fn text_input_create_system(
+    mut commands: Commands,
+    query: Query<
+        (
+            Entity,
+            &PlaceholderText,
+            &InputTextStyle,
+            &InputTextAlignment,
+            Option<&PlaceholderAlignment>,
+            &TextInputValue,
+            Option<&TextInputMask>,
+            &Style,
+        ),
+        Added<PlaceholderText>,
+    >,
+) {
+    for (entity, placeholder, style, alignment, placeholder_alignment, value, mask, node_style) in
+        query.iter()
+    {
+        let placeholder_alignment = placeholder_alignment.map_or(alignment.0, |a| a.0);
+        commands.entity(entity).with_children(|parent| {
+            parent
+                .spawn_bundle(TextBundle {
+                    style: Style {
+                        position: inner_text_position(node_style),
+                        position_type: PositionType::Absolute,
+                        ..Default::default()
+                    },
+                    text: placeholder.0.clone().with_alignment(placeholder_alignment),
+                    visibility: Visibility {
+                        is_visible: value.is_empty(),
+                    },
+                    ..Default::default()
+                })
+                .insert(TextInputPlaceholder);
+
+            parent
+                .spawn_bundle(TextBundle {
+                    style: Style {
+                        position: inner_text_position(node_style),
+                        position_type: PositionType::Absolute,
+                        ..Default::default()
+                    },
+                    text: Text::from_section(masked(&value.0, mask), style.0.clone())
+                        .with_alignment(alignment.0),
+                    ..Default::default()
+                })
+                .insert(TextInputInner);
+        });
+    }
+}
+
+/// Keeps the inner text and the placeholder inside the input's padding and border
+pub fn text_input_padding_system(
+    query: Query<(Entity, &Style), (With<PlaceholderText>, Changed<Style>)>,
+    mut children: Query<
+        (&Parent, &mut Style),
+        (
+            Or<(With<TextInputPlaceholder>, With<TextInputInner>)>,
+            Without<PlaceholderText>,
+        ),
+    >,
+) {
+    for (entity, node_style) in query.iter() {
+        for (_, mut style) in children
+            .iter_mut()
+            .filter(|(parent, _)| parent.get() == entity)
+        {
+            style.position = inner_text_position(node_style);
+        }
+    }
+}
+
+/// Absolute position of the text children: the input's border box inset by its padding and border
+fn inner_text_position(node_style: &Style) -> UiRect<Val> {
+    UiRect {
+        left: add_vals(node_style.padding.left, node_style.border.left),
+        right: add_vals(node_style.padding.right, node_style.border.right),
+        top: add_vals(node_style.padding.top, node_style.border.top),
+        bottom: add_vals(node_style.padding.bottom, node_style.border.bottom),
+    }
+}
+
+/// Sum of two offsets. `Undefined` and `Auto` count as zero. A pixel and a percent offset
+/// can't be added without the parent's size, so the pixel one is kept and the percent one ignored
+fn add_vals(a: Val, b: Val) -> Val {
+    match (a, b) {
+        (Val::Px(a), Val::Px(b)) => Val::Px(a + b),
+        (Val::Percent(a), Val::Percent(b)) => Val::Percent(a + b),
+        (Val::Px(px), Val::Percent(_)) | (Val::Percent(_), Val::Px(px)) => Val::Px(px),
+        (Val::Undefined | Val::Auto, Val::Undefined | Val::Auto) => Val::Px(0.0),
+        (Val::Undefined | Val::Auto, val) | (val, Val::Undefined | Val::Auto) => val,
+    }
+}
+
+/// Scrolls a multiline text input to keep the cursor line in view. PageUp and PageDown move
+/// the cursor a viewport at a time. Set [`Style::overflow`] to [`Overflow::Hidden`]
+/// on the input to clip the lines that are out of view
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq)]
+pub struct TextInputScroll {
+    /// How far the text is shifted down from where its [`InputTextAlignment`] puts it,
+    /// in logical pixels. Top-aligned text scrolls to later lines with negative offsets,
+    /// bottom-aligned text scrolls to earlier lines with positive ones
+    pub offset: f32,
+}
+
+/// A thin bar at the right edge of a text input with [`TextInputScroll`] that shows which part
+/// of the text is in view. It's hidden when all the text fits
+#[derive(Component, Clone, Copy, Debug)]
+pub struct TextInputScrollbar {
+    /// Width of the bar. Default is 4px
+    pub width: Val,
+    /// Color of the bar
+    pub color: UiColor,
+}
+
+impl Default for TextInputScrollbar {
+    fn default() -> Self {
+        Self {
+            width: Val::Px(4.0),
+            color: Color::rgba(1.0, 1.0, 1.0, 0.4).into(),
+        }
+    }
+}
+
+/// The node spawned for [`TextInputScrollbar`]
+#[derive(Component)]
+pub struct TextInputScrollbarThumb;
+
+pub fn text_input_scrollbar_create_system(
+    mut commands: Commands,
+    query: Query<(Entity, &TextInputScrollbar), Added<TextInputScrollbar>>,
+) {
+    for (entity, scrollbar) in query.iter() {
+        commands.entity(entity).with_children(|parent| {
+            parent
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        position: UiRect {
+                            right: Val::Px(0.0),
+                            top: Val::Px(0.0),
+                            ..Default::default()
+                        },
+                        position_type: PositionType::Absolute,
+                        size: Size::new(scrollbar.width, Val::Percent(0.0)),
+                        ..Default::default()
+                    },
+                    color: scrollbar.color,
+                    visibility: Visibility { is_visible: false },
+                    ..Default::default()
+                })
+                .insert(FocusPolicy::Pass)
+                .insert(TextInputScrollbarThumb);
+        });
+    }
+}
+
+pub fn text_input_scroll_system(
+    fonts: Res<Assets<Font>>,
+    keyboard: Res<Input<KeyCode>>,
+    windows: Res<Windows>,
+    mut query: Query<(
+        Entity,
+        &mut TextInputScroll,
+        &mut TextInputFocus,
+        Option<&mut TextInputSelection>,
+        &TextInputValue,
+        &InputTextStyle,
+        &Node,
+        &Style,
+    )>,
+    mut query_text: Query<
+        (&Parent, &Text, &mut Style),
+        (With<TextInputInner>, Without<TextInputScroll>),
+    >,
+    mut query_thumbs: Query<
+        (&Parent, &mut Style, &mut Visibility),
+        (
+            With<TextInputScrollbarThumb>,
+            Without<TextInputInner>,
+            Without<TextInputScroll>,
+        ),
+    >,
+) {
+    let scale_factor = windows
+        .get_primary()
+        .map_or(1.0, |window| window.scale_factor() as f32);
+    let page_up = keyboard.just_pressed(KeyCode::PageUp);
+    let page_down = keyboard.just_pressed(KeyCode::PageDown);
+    let shift = keyboard.any_pressed([KeyCode::LShift, KeyCode::RShift]);
+    for (entity, mut scroll, mut focus, selection, value, text_style, node, node_style) in
+        query.iter_mut()
+    {
+        let (_, text, mut text_node_style) = match query_text
+            .iter_mut()
+            .find(|(parent, ..)| parent.get() == entity)
+        {
+            Some(text) => text,
+            None => continue,
+        };
+        let font = match fonts.get(&text_style.0.font) {
+            Some(font) => font.font.clone(),
+            None => continue,
+        };
+        let physical_scale = PxScale::from(text.sections[0].style.font_size * scale_factor);
+        let line_height = line_height(&font, physical_scale) / scale_factor;
+        let px = |val: Val| match val {
+            Val::Px(px) => px,
+            _ => 0.0,
+        };
+        let visible_height = node.size.y
+            - px(node_style.padding.top)
+            - px(node_style.padding.bottom)
+            - px(node_style.border.top)
+            - px(node_style.border.bottom);
+        let visible_lines = ((visible_height / line_height) as usize).max(1);
+        let lines_total = value.split('\n').count();
+        let text_height = lines_total as f32 * line_height;
+        let anchor = text_anchor(&text.alignment);
+
+        let mut offset = scroll.offset;
+        if let Some(cursor) = focus.0 {
+            let mut new_cursor = cursor;
+            if page_up {
+                new_cursor = move_lines(value, new_cursor, -(visible_lines as isize));
+            }
+            if page_down {
+                new_cursor = move_lines(value, new_cursor, visible_lines as isize);
+            }
+            if new_cursor != cursor {
+                if let Some(mut selection) = selection {
+                    if shift {
+                        selection.anchor.get_or_insert(cursor);
+                    } else if selection.anchor.is_some() {
+                        selection.anchor = None;
+                    }
+                }
+                focus.0 = Some(new_cursor);
+            }
+            let top = line_top_in_view(
+                value,
+                byte_index(value, new_cursor),
+                anchor,
+                line_height,
+                visible_height,
+                offset,
+            );
+            offset = scroll_into_view(offset, top, line_height, visible_height);
+        }
+        let (min_offset, max_offset) = scroll_range(text_height, visible_height, anchor);
+        let offset = offset.clamp(min_offset, max_offset);
+        if (scroll.offset - offset).abs() > f32::EPSILON {
+            scroll.offset = offset;
+        }
+
+        // the padding system resets the position when the style changes, so always reapply
+        let position = inner_text_position(node_style);
+        let top = add_vals(position.top, Val::Px(offset));
+        let bottom = add_vals(position.bottom, Val::Px(-offset));
+        if text_node_style.position.top != top || text_node_style.position.bottom != bottom {
+            text_node_style.position.top = top;
+            text_node_style.position.bottom = bottom;
+        }
+
+        for (_, mut thumb_style, mut visibility) in query_thumbs
+            .iter_mut()
+            .filter(|(parent, ..)| parent.get() == entity)
+        {
+            let fits = max_offset - min_offset <= f32::EPSILON;
+            if visibility.is_visible == fits {
+                visibility.is_visible = !fits;
+            }
+            if !fits {
+                let height = Val::Percent(visible_height / text_height * 100.0);
+                // the part of the text above the view
+                let top = Val::Percent((max_offset - offset) / text_height * 100.0);
+                if thumb_style.size.height != height || thumb_style.position.top != top {
+                    thumb_style.size.height = height;
+                    thumb_style.position.top = top;
+                }
+            }
+        }
+    }
+}
+
+/// Moves the cursor `lines` lines down, or up if negative, keeping the character column
+fn move_lines(value: &str, cursor: usize, lines: isize) -> usize {
+    // the value may have been shortened by an app after the cursor was placed
+    let cursor = cursor.min(value.chars().count());
+    let line_lengths = value
+        .split('\n')
+        .map(|line| line.chars().count())
+        .collect::<Vec<_>>();
+    let mut line = 0;
+    let mut line_start = 0;
+    while cursor > line_start + line_lengths[line] {
+        line_start += line_lengths[line] + 1;
+        line += 1;
+    }
+    let column = cursor - line_start;
+    let target = (line as isize + lines).clamp(0, line_lengths.len() as isize - 1) as usize;
+    let target_start = line_lengths[..target]
+        .iter()
+        .map(|length| length + 1)
+        .sum::<usize>();
+    target_start + column.min(line_lengths[target])
+}
+
+/// Non-editable text around the value, like "$ 100" or "100 kg".
+/// The prefix is displayed before the value and shifts it, and the suffix follows the end of the value
+#[derive(Component, Clone, Debug, Default)]
+pub struct InputAdornment {
+    /// Text displayed before the value. Changing it updates the prefix node,
+    /// but a prefix that was `None` when the component was added is never spawned
+    pub prefix: Option<Text>,
+    /// Text displayed after the value. Changing it updates the suffix node,
+    /// but a suffix that was `None` when the component was added is never spawned
+    pub suffix: Option<Text>,
+    prefix_width: f32,
+}
+
+impl InputAdornment {
+    /// Text displayed before the value
+    pub fn with_prefix(mut self, prefix: Text) -> Self {
+        self.prefix = Some(prefix);
+        self
+    }
+
+    /// Text displayed after the value
+    pub fn with_suffix(mut self, suffix: Text) -> Self {
+        self.suffix = Some(suffix);
+        self
+    }
+}
+
+/// Marks [`InputAdornment`]'s prefix text node
+#[derive(Component)]
+pub struct TextInputPrefix;
+/// Marks [`InputAdornment`]'s suffix text node
+#[derive(Component)]
+pub struct TextInputSuffix;
+
+pub fn text_input_adornment_create_system(
+    mut commands: Commands,
+    query: Query<(Entity, &InputAdornment, &Style), Added<InputAdornment>>,
+) {
+    for (entity, adornment, node_style) in query.iter() {
+        commands.entity(entity).with_children(|parent| {
+            if let Some(prefix) = &adornment.prefix {
+                parent
+                    .spawn_bundle(TextBundle {
+                        style: Style {
+                            position: inner_text_position(node_style),
+                            position_type: PositionType::Absolute,
+                            ..Default::default()
+                        },
+                        text: prefix.clone(),
+                        ..Default::default()
+                    })
+                    .insert(TextInputPrefix);
+            }
+            if let Some(suffix) = &adornment.suffix {
+                parent
+                    .spawn_bundle(TextBundle {
+                        style: Style {
+                            // the width comes from the text, the left edge follows the value
+                            position: UiRect {
+                                right: Val::Undefined,
+                                ..inner_text_position(node_style)
+                            },
+                            position_type: PositionType::Absolute,
+                            ..Default::default()
+                        },
+                        text: suffix.clone(),
+                        ..Default::default()
+                    })
+                    .insert(TextInputSuffix);
+            }
+        });
+    }
+}
+
+/// Shifts the value by the prefix width and moves the suffix to the end of the value
+pub fn text_input_adornment_system(
+    fonts: Res<Assets<Font>>,
+    mut glyph_calculators: ResMut<GlyphCalculatorCache>,
+    mut font_events: EventReader<AssetEvent<Font>>,
+    mut query: Query<(
+        Entity,
+        ChangeTrackers<InputAdornment>,
+        ChangeTrackers<TextInputValue>,
+        ChangeTrackers<Style>,
+        &mut InputAdornment,
+        &InputTextStyle,
+        Option<&FallbackFonts>,
+        &TextInputValue,
+        Option<&TextInputMask>,
+        &Style,
+    )>,
+    mut children: Query<
+        (
+            &Parent,
+            &mut Style,
+            &mut Text,
+            Option<&TextInputInner>,
+            Option<&TextInputPrefix>,
+            Option<&TextInputSuffix>,
+        ),
+        (
+            Or<(
+                With<TextInputInner>,
+                With<TextInputPlaceholder>,
+                With<TextInputPrefix>,
+                With<TextInputSuffix>,
+            )>,
+            Without<InputAdornment>,
+        ),
+    >,
+    windows: Res<Windows>,
+) {
+    // a font that finished loading can change any measurement
+    let fonts_changed = font_events.iter().count() > 0;
+    let scale_factor = windows
+        .get_primary()
+        .map_or(1.0, |window| window.scale_factor() as f32);
+    for (
+        entity,
+        adornment_tracker,
+        value_tracker,
+        style_tracker,
+        mut adornment,
+        text_style,
+        fallback_fonts,
+        value,
+        mask,
+        node_style,
+    ) in query.iter_mut()
+    {
+        // the inner text changes with the value, the mask and the font size
+        let inner_text = children
+            .iter_mut()
+            .find(|(parent, _, _, inner, ..)| parent.get() == entity && inner.is_some())
+            .map(|(_, _, text, ..)| {
+                let font_size = text.sections.first().map(|section| section.style.font_size);
+                (text.is_changed(), font_size)
+            });
+        let (inner_changed, font_size) = match inner_text {
+            Some((changed, Some(font_size))) => (changed, font_size),
+            _ => continue,
+        };
+        let adornment_changed = adornment_tracker.is_changed();
+        if !(fonts_changed
+            || inner_changed
+            || adornment_changed
+            || value_tracker.is_changed()
+            || style_tracker.is_changed())
+        {
+            continue;
+        }
+
+        let prefix_width = match &adornment.prefix {
+            Some(prefix) => match text_node_width(&fonts, &mut glyph_calculators, prefix) {
+                Some(width) => width,
+                None => continue,
+            },
+            None => 0.0,
+        };
+        let calculator = match glyph_calculators.get(&fonts, &text_style.0.font, fallback_fonts) {
+            Some(calculator) => calculator,
+            None => continue,
+        };
+        // measured like the cursor, so the suffix starts where the cursor at the end would be
+        let value_width = text_width_with_fallback(
+            masked(&value.0, mask)
+                .split('\n')
+                .next_back()
+                .unwrap_or_default(),
+            calculator,
+            PxScale::from(font_size * scale_factor),
+        ) / scale_factor;
+        if (adornment.prefix_width - prefix_width).abs() > f32::EPSILON {
+            adornment.prefix_width = prefix_width;
+        }
+
+        let inset = inner_text_position(node_style);
+        for (_, mut style, mut text, _, prefix, suffix) in children
+            .iter_mut()
+            .filter(|(parent, ..)| parent.get() == entity)
+        {
+            if adornment_changed {
+                let adornment_text = match (prefix, suffix) {
+                    (Some(_), _) => adornment.prefix.as_ref(),
+                    (_, Some(_)) => adornment.suffix.as_ref(),
+                    _ => None,
+                };
+                if let Some(adornment_text) = adornment_text {
+                    *text = adornment_text.clone();
+                }
+            }
+            if prefix.is_some() {
+                continue;
+            }
+            let shift = if suffix.is_some() {
+                prefix_width + value_width
+            } else {
+                prefix_width
+            };
+            let left = add_vals(inset.left, Val::Px(shift));
+            if style.position.left != left {
+                style.position.left = left;
+            }
+        }
+    }
+}
+
+/// Width of the first line of `text`, measured with its first section's font
+fn text_node_width(
+    fonts: &Assets<Font>,
+    glyph_calculators: &mut GlyphCalculatorCache,
+    text: &Text,
+) -> Option<f32> {
+    let style = &text.sections.first()?.style;
+    let calculator = glyph_calculators.get(fonts, &style.font, None)?;
+    let value = text
+        .sections
+        .iter()
+        .map(|section| section.value.as_str())
+        .collect::<String>();
+    Some(text_width(
+        value.split('\n').next().unwrap_or_default(),
+        calculator,
+        PxScale::from(style.font_size),
+    ))
+}
+
+/// Hides the placeholder while the input is focused, even if the value is empty.
+/// By default, the placeholder is only hidden when the value isn't empty
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct PlaceholderHideOnFocus;
+
+pub fn text_input_placeholder_focus_system(
+    query: Query<
+        (Entity, &TextInputValue, &TextInputFocus),
+        (
+            With<PlaceholderHideOnFocus>,
+            Or<(Changed<TextInputFocus>, Changed<TextInputValue>)>,
+        ),
+    >,
+    mut placeholder_query: Query<(&Parent, &mut Visibility), With<TextInputPlaceholder>>,
+) {
+    for (entity, value, focus) in query.iter() {
+        for (parent, mut visibility) in placeholder_query.iter_mut() {
+            if parent.get() == entity {
+                visibility.is_visible = value.is_empty() && focus.0.is_none();
+            }
+        }
+    }
+}
+
+pub fn text_input_update_system(
+    fonts: Res<Assets<Font>>,
+    query: Query<
+        (
+            Entity,
+            &TextInputValue,
+            &InputTextStyle,
+            Option<&FallbackFonts>,
+            Option<&TextInputMask>,
+        ),
+        Or<(Changed<TextInputValue>, Changed<TextInputMask>)>,
+    >,
+    mut placeholder_query: Query<(&Parent, &mut Visibility), With<TextInputPlaceholder>>,
+    mut value_query: Query<(&Parent, &mut Text), With<TextInputInner>>,
+) {
+    for (entity, value, text_style, fallback_fonts, mask) in query.iter() {
+        if let Some((_, mut placeholder_visibility)) = placeholder_query
+            .iter_mut()
+            .find(|(parent, _)| parent.get() == entity)
+        {
+            let mut inner_text = value_query
+                .iter_mut()
+                .find(|(parent, _)| parent.get() == entity)
+                .unwrap()
+                .1;
+            placeholder_visibility.is_visible = value.is_empty();
+            let mut displayed = masked(&value.0, mask).into_owned();
+            if displayed.ends_with('\n') {
+                displayed.push(' ');
+            }
+            let font_list = fallback_fonts
+                .and_then(|fallback| font_list(&fonts, &text_style.0.font, Some(fallback)));
+            match (fallback_fonts, font_list) {
+                (Some(fallback), Some(font_list)) => {
+                    // split the value into sections, each one with a font that has its glyphs
+                    let handles = std::iter::once(&text_style.0.font)
+                        .chain(fallback.0.iter())
+                        .collect::<Vec<_>>();
+                    let font_size = inner_text.sections[0].style.font_size;
+                    inner_text.sections = font_runs(&displayed, &font_list)
+                        .into_iter()
+                        .map(|(font, run)| TextSection {
+                            value: run.to_string(),
+                            style: TextStyle {
+                                font: handles[font].clone(),
+                                font_size,
+                                color: text_style.0.color,
+                            },
+                        })
+                        .collect();
+                }
+                _ => {
+                    inner_text.sections.truncate(1);
+                    inner_text.sections[0].value = displayed;
+                }
+            }
+        }
+    }
+}
+
+/// Hides the value, e.g. for passwords. The real value stays in [`TextInputValue`],
+/// and copying or cutting a masked value is disabled
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TextInputMask {
+    /// The value is displayed as is
+    #[default]
+    None,
+    /// Every character except new lines is displayed as this character, e.g. '•'
+    Char(char),
+}
+
+impl TextInputMask {
+    /// The text that is displayed for `value`
+    /// ```
+    /// # use bevy_slimy_widgets::text_input::TextInputMask;
+    /// assert_eq!(TextInputMask::Char('*').apply("hunter2"), "*******");
+    /// ```
+    pub fn apply<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        match *self {
+            TextInputMask::None => Cow::Borrowed(value),
+            TextInputMask::Char(mask) => Cow::Owned(
+                value
+                    .chars()
+                    .map(|ch| if ch == '\n' { ch } else { mask })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+fn masked<'a>(value: &'a str, mask: Option<&TextInputMask>) -> Cow<'a, str> {
+    mask.map_or(Cow::Borrowed(value), |mask| mask.apply(value))
+}
+
+/// Whether a text input can be edited or focused. Without this component, the input is enabled
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TextInputStatus {
+    /// The input can be focused and edited
+    #[default]
+    Enabled,
+    /// The input can be focused to move the cursor, select and copy text, but not edited.
+    /// The selection is cleared when it becomes read-only
+    ReadOnly,
+    /// The input can't be focused. It's unfocused and its selection is cleared
+    /// when it becomes disabled
+    Disabled,
+}
+
+/// Colors the text input node depending on whether it's [`TextInputStatus::Disabled`]
+#[derive(Component, Clone, Copy, Debug)]
+pub struct DisabledColor {
+    /// Node color while the input is disabled
+    pub disabled: UiColor,
+    /// Node color while the input is enabled or read-only
+    pub enabled: UiColor,
+}
+
+pub fn text_input_status_system(
+    mut query: Query<
+        (
+            &TextInputStatus,
+            &mut TextInputFocus,
+            Option<&mut TextInputSelection>,
+            Option<&DisabledColor>,
+            &mut UiColor,
+        ),
+        Changed<TextInputStatus>,
+    >,
+) {
+    for (status, mut focus, selection, disabled_color, mut color) in query.iter_mut() {
+        let disabled = *status == TextInputStatus::Disabled;
+        if disabled && focus.0.is_some() {
+            focus.0 = None;
+        }
+        if let Some(mut selection) = selection {
+            if *status != TextInputStatus::Enabled && selection.anchor.is_some() {
+                selection.anchor = None;
+            }
+        }
+        if let Some(disabled_color) = disabled_color {
+            *color = if disabled {
+                disabled_color.disabled
+            } else {
+                disabled_color.enabled
+            };
+        }
+    }
+}
+
+/// Blocks editing without losing focus, e.g. while the value is validated by a server.
+/// Unlike unfocusing, the cursor stays visible, so the input can be unlocked seamlessly
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct InputLocked;
+
+/// Keys that trigger a text input action. If any of the keys is pressed, the action is triggered
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Keybind(pub Vec<KeyCode>);
+
+impl Keybind {
+    fn pressed(&self, keys: &[KeyCode]) -> bool {
+        self.0.iter().any(|key| keys.contains(key))
+    }
+}
+
+impl From<KeyCode> for Keybind {
+    fn from(key: KeyCode) -> Self {
+        Self(vec![key])
+    }
+}
+
+/// A resource that maps text input cursor movement to keys, for apps with custom input remapping
+#[derive(Clone, Debug)]
+pub struct TextInputKeybinds {
+    /// Move the cursor one character left. Default is [`KeyCode::Left`]
+    pub move_left: Keybind,
+    /// Move the cursor one character right. Default is [`KeyCode::Right`]
+    pub move_right: Keybind,
+    /// Move the cursor to the start of the line. Default is [`KeyCode::Home`]
+    pub line_start: Keybind,
+    /// Move the cursor to the end of the line. Default is [`KeyCode::End`]
+    pub line_end: Keybind,
+    /// Move the cursor to the previous line. Default is [`KeyCode::Up`]
+    pub line_up: Keybind,
+    /// Move the cursor to the next line. Default is [`KeyCode::Down`]
+    pub line_down: Keybind,
+}
+
+impl Default for TextInputKeybinds {
+    fn default() -> Self {
+        Self {
+            move_left: KeyCode::Left.into(),
+            move_right: KeyCode::Right.into(),
+            line_start: KeyCode::Home.into(),
+            line_end: KeyCode::End.into(),
+            line_up: KeyCode::Up.into(),
+            line_down: KeyCode::Down.into(),
+        }
+    }
+}
+
+/// A resource with the timing of repeated actions while a key is held. Applies to Backspace, Delete
+/// and the keys of [`TextInputKeybinds`], typed characters are repeated by the OS.
+/// Default is a 500ms delay and a 30ms interval. An action repeats at most once per frame
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyRepeatConfig {
+    /// How long a key is held before the action repeats
+    pub delay: Duration,
+    /// Time between repeats
+    pub interval: Duration,
+}
+
+impl Default for KeyRepeatConfig {
+    fn default() -> Self {
+        Self {
+            delay: Duration::from_millis(500),
+            interval: Duration::from_millis(30),
+        }
+    }
+}
+
+impl KeyRepeatConfig {
+    /// How many times an action repeats when its key is held for `held`
+    fn repeats(&self, held: Duration) -> u128 {
+        if held < self.delay {
+            0
+        } else {
+            1 + (held - self.delay).as_nanos() / self.interval.as_nanos().max(1)
+        }
+    }
+}
+
+/// Updates how long the repeatable keys have been held, and returns the ones that repeat this frame
+fn repeated_keys(
+    keyboard: &Input<KeyCode>,
+    keybinds: &TextInputKeybinds,
+    config: &KeyRepeatConfig,
+    delta: Duration,
+    held_keys: &mut HashMap<KeyCode, Duration>,
+) -> Vec<KeyCode> {
+    let repeatable = [
+        &keybinds.move_left,
+        &keybinds.move_right,
+        &keybinds.line_start,
+        &keybinds.line_end,
+        &keybinds.line_up,
+        &keybinds.line_down,
+    ]
+    .into_iter()
+    .flat_map(|keybind| keybind.0.iter().copied())
+    .chain([KeyCode::Back, KeyCode::Delete]);
+    let mut repeated = Vec::new();
+    for key in repeatable {
+        if !keyboard.pressed(key) || keyboard.just_pressed(key) {
+            held_keys.remove(&key);
+            continue;
+        }
+        let held = held_keys.entry(key).or_default();
+        let before = config.repeats(*held);
+        *held += delta;
+        if config.repeats(*held) > before {
+            repeated.push(key);
+        }
+    }
+    repeated
+}
+
+/// Clipboard used by text inputs. Implement it to copy and paste with something other than
+/// the system clipboard, e.g. an in-memory clipboard in tests, and put it into the
+/// [`TextInputClipboard`] resource
+pub trait ClipboardAccess: Send + Sync + 'static {
+    /// Returns the clipboard contents, or `None` if it's empty or unavailable
+    fn get_contents(&mut self) -> Option<String>;
+    /// Replaces the clipboard contents
+    fn set_contents(&mut self, contents: String);
+}
+
+/// The system clipboard. Requires the `clipboard` feature
+#[cfg(feature = "clipboard")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClipboard;
+
+#[cfg(feature = "clipboard")]
+impl ClipboardAccess for SystemClipboard {
+    fn get_contents(&mut self) -> Option<String> {
+        ClipboardContext::new().ok()?.get_contents().ok()
+    }
+
+    fn set_contents(&mut self, contents: String) {
+        if let Ok(mut clipboard) = ClipboardContext::new() {
+            let _ = clipboard.set_contents(contents);
+        }
+    }
+}
+
+/// A clipboard that is always empty and ignores copied text, so pasting does nothing
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoClipboard;
+
+impl ClipboardAccess for NoClipboard {
+    fn get_contents(&mut self) -> Option<String> {
+        None
+    }
+
+    fn set_contents(&mut self, _contents: String) {}
+}
+
+/// A clipboard that only exists inside the app, for tests that shouldn't touch the system clipboard
+#[cfg(feature = "test-utils")]
+#[derive(Clone, Debug, Default)]
+pub struct InMemoryClipboard(pub Option<String>);
+
+#[cfg(feature = "test-utils")]
+impl ClipboardAccess for InMemoryClipboard {
+    fn get_contents(&mut self) -> Option<String> {
+        self.0.clone()
+    }
+
+    fn set_contents(&mut self, contents: String) {
+        self.0 = Some(contents);
+    }
+}
+
+/// A resource with the clipboard that text inputs copy to and paste from.
+/// Defaults to `SystemClipboard`, or to [`NoClipboard`] without the `clipboard` feature
+pub struct TextInputClipboard(pub Box<dyn ClipboardAccess>);
+
+impl Default for TextInputClipboard {
+    #[cfg(feature = "clipboard")]
+    fn default() -> Self {
+        Self(Box::new(SystemClipboard))
+    }
+
+    #[cfg(not(feature = "clipboard"))]
+    fn default() -> Self {
+        Self(Box::new(NoClipboard))
+    }
+}
+
+/// Sent when [`TextInputConstrains`] reject the keyboard input of a frame, so the value is unchanged
+#[derive(Clone, Debug)]
+pub struct TextInputRejected {
+    /// The text input entity
+    pub entity: Entity,
+    /// [`TextInputConstrain::reason`] of the constrain that rejected the input
+    pub reason: Option<String>,
+}
+
+/// Sent when a paste didn't fit into [`TextInputConstrains`], so only a part of it was pasted.
+/// Not sent if [`EditGuard`] or the constrains cancel the paste altogether
+#[derive(Clone, Debug)]
+pub struct TextInputTruncated {
+    /// The text input entity
+    pub entity: Entity,
+    /// The part of the clipboard contents that wasn't pasted
+    pub dropped: String,
+}
+
+/// Decides which pasted characters are kept, replacing the default filter.
+/// By default, control characters are stripped, except for new lines in [`InputMode::Multiline`]
+#[derive(Component)]
+pub struct PasteFilter(pub Box<dyn Fn(char) -> bool + Send + Sync>);
+
+impl PasteFilter {
+    /// Creates a filter that keeps characters for which `filter` returns `true`
+    pub fn new(filter: impl Fn(char) -> bool + Send + Sync + 'static) -> Self {
+        Self(Box::new(filter))
+    }
+}
+
+/// Maps or drops each typed or pasted character before [`TextInputConstrains`] are checked,
+/// e.g. to force uppercase. Returning `None` drops the character
+#[derive(Component)]
+pub struct OnInsertTransform(pub Box<dyn Fn(char) -> Option<char> + Send + Sync>);
+
+impl OnInsertTransform {
+    /// Creates a transform from a function
+    pub fn new(transform: impl Fn(char) -> Option<char> + Send + Sync + 'static) -> Self {
+        Self(Box::new(transform))
+    }
+}
+
+fn transform_inserted(text: &str, transform: Option<&OnInsertTransform>) -> String {
+    match transform {
+        Some(transform) => text.chars().filter_map(|ch| (transform.0)(ch)).collect(),
+        None => text.to_string(),
+    }
+}
+
+/// An edit that [`EditGuard`] can cancel. Positions are character indices into [`TextInputValue`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EditOp {
+    /// Typed text, a new line or a dropped file path inserted at `at`
+    Insert {
+        /// Where the text is inserted
+        at: usize,
+        /// The inserted text
+        text: String,
+    },
+    /// Pasted text inserted at `at`
+    Paste {
+        /// Where the text is inserted
+        at: usize,
+        /// The inserted text, after [`PasteFilter`], [`OnInsertTransform`] and truncation
+        text: String,
+    },
+    /// Backspace removing `range`
+    Backspace {
+        /// The removed part of the value
+        range: Range<usize>,
+    },
+    /// Delete removing `range`
+    Delete {
+        /// The removed part of the value
+        range: Range<usize>,
+    },
+}
+
+/// Cancels edits based on the operation rather than the resulting value, e.g. to stop
+/// deleting a fixed prefix. Returning `false` cancels only that operation,
+/// the rest of the frame's input is still applied
+#[derive(Component)]
+pub struct EditGuard(pub Box<dyn Fn(&EditOp) -> bool + Send + Sync>);
+
+impl EditGuard {
+    /// Creates a guard that allows edits for which `guard` returns `true`
+    pub fn new(guard: impl Fn(&EditOp) -> bool + Send + Sync + 'static) -> Self {
+        Self(Box::new(guard))
+    }
+
+    fn allows(guard: Option<&EditGuard>, op: &EditOp) -> bool {
+        guard.is_none_or(|guard| (guard.0)(op))
+    }
+}
+
+fn keep_pasted_char(ch: char, mode: InputMode, filter: Option<&PasteFilter>) -> bool {
+    match filter {
+        Some(filter) => (filter.0)(ch),
+        None => !ch.is_control() || (ch == '\n' && mode == InputMode::Multiline),
+    }
+}
+
+/// Undo (Ctrl-Z) and redo (Ctrl-Y or Ctrl-Shift-Z) history of a text input.
+/// A run of typed characters is undone as one step
+#[derive(Component, Clone, Debug)]
+pub struct TextInputHistory {
+    /// How many undo steps are kept. Default is 100
+    pub max_history: usize,
+    undo: Vec<String>,
+    redo: Vec<String>,
+    typing: bool,
+}
+
+impl Default for TextInputHistory {
+    fn default() -> Self {
+        Self::new(100)
+    }
+}
+
+impl TextInputHistory {
+    /// Creates an empty history that keeps up to `max_history` undo steps
+    pub fn new(max_history: usize) -> Self {
+        Self {
+            max_history,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            typing: false,
+        }
+    }
+
+    /// Saves `old` value before an edit. Consecutive typing is merged into one step
+    fn record(&mut self, old: &str, typing: bool) {
+        if !(typing && self.typing) {
+            self.undo.push(old.to_string());
+            if self.undo.len() > self.max_history {
+                let excess = self.undo.len() - self.max_history;
+                self.undo.drain(..excess);
+            }
+        }
+        self.redo.clear();
+        self.typing = typing;
+    }
+
+    fn undo(&mut self, current: &str) -> Option<String> {
+        let previous = self.undo.pop()?;
+        self.redo.push(current.to_string());
+        self.typing = false;
+        Some(previous)
+    }
+
+    fn redo(&mut self, current: &str) -> Option<String> {
+        let next = self.redo.pop()?;
+        self.undo.push(current.to_string());
+        self.typing = false;
+        Some(next)
+    }
+
+    /// Forgets all undo and redo steps, e.g. when the value is reset
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.typing = false;
+    }
+}
+
+/// Handles keyboard input of focused text inputs.
+///
+/// Escape is handled first and replaces all other input in the frame, unless the input has
+/// [`EscapeBehavior::Ignore`]. Undo and redo from [`TextInputHistory`] replace it as well.
+/// All other keyboard events received in one frame are applied in a fixed order, regardless of
+/// the order they arrived in, so replaying the same events always gives the same result:
+/// 1. Enter (inserts a new line in [`InputMode::Multiline`], sends [`TextInputSubmit`] otherwise)
+/// 2. Ctrl-A, then Ctrl-E
+/// 3. Copy (Ctrl-C) and cut (Ctrl-X) of the selected text, or the whole value if nothing is selected
+/// 4. Backspace, then Delete. With Ctrl held, they delete to the previous or next word boundary
+/// 5. Paste (Ctrl-V), with characters rejected by [`PasteFilter`] stripped
+/// 6. Cursor movement from [`TextInputKeybinds`]: left, right, line start, line end, up, down.
+///    With Ctrl held, left and right move by whole words.
+///    With Shift held, the movement extends [`TextInputSelection`]
+/// 7. Insert, toggling [`TextInputOvertype`]
+/// 8. Typed characters, inserted at the cursor position after all of the above,
+///    or replacing the characters after it if overtype is on
+///
+/// Typed and pasted characters go through [`OnInsertTransform`] before being inserted,
+/// replacing the selected text. Backspace and Delete remove only the selection if there is one.
+/// Each edit can be cancelled separately by [`EditGuard`].
+///
+/// Each action is applied at most once per frame, even if its key was pressed several times.
+/// Held Backspace, Delete and [`TextInputKeybinds`] keys repeat at the [`KeyRepeatConfig`] rate
+/// instead of the OS one, and a repeat is applied like a press in its place in the order.
+/// The new value is tested against [`TextInputConstrains`] once at the end, so if it's
+/// rejected, the whole frame's edit is dropped and [`TextInputRejected`] is sent.
+pub fn text_input_system(
+    // grouped because a system can't have more than 16 parameters
+    (fonts, mut glyph_calculators): (Res<Assets<Font>>, ResMut<GlyphCalculatorCache>),
+    mut query: Query<
+        (
+            Entity,
+            &InputTextStyle,
+            &mut TextInputValue,
+            &mut TextInputFocus,
+            &TextInputConstrains,
+            &InputMode,
+            Option<&PasteFilter>,
+            Option<&OnInsertTransform>,
+            Option<&EditGuard>,
+            Option<&mut TextInputSelection>,
+            Option<&mut TextInputHistory>,
+            Option<&TextInputMask>,
+            Option<&TextInputStatus>,
+            Option<&mut TextInputOvertype>,
+            Option<&EscapeBehavior>,
+        ),
+        Without<InputLocked>,
+    >,
+    mut cursors: Query<(&Parent, &mut Visibility, &mut BlinkingTimer)>,
+    mut input: EventReader<KeyboardInput>,
+    keyboard: Res<Input<KeyCode>>,
+    mut char_evr: EventReader<ReceivedCharacter>,
+    mut truncated: EventWriter<TextInputTruncated>,
+    mut rejected: EventWriter<TextInputRejected>,
+    mut submit: EventWriter<TextInputSubmit>,
+    keybinds: Res<TextInputKeybinds>,
+    mut clipboard: ResMut<TextInputClipboard>,
+    mut warned_no_font: Local<bool>,
+    time: Res<Time>,
+    key_repeat: Res<KeyRepeatConfig>,
+    mut held_keys: Local<HashMap<KeyCode, Duration>>,
+) {
+    // the OS repeats held keys too, but at its own rate, so those presses are ignored
+    let held_before = held_keys.clone();
+    let repeated = repeated_keys(
+        &keyboard,
+        &keybinds,
+        &key_repeat,
+        time.delta(),
+        &mut held_keys,
+    );
+    let mut keys = input
+        .iter()
+        .filter(|key| key.state == ButtonState::Pressed)
+        .filter_map(|key| key.key_code)
+        .filter(|key| !held_before.contains_key(key))
+        .collect::<Vec<_>>();
+    keys.extend(repeated.iter().copied());
+    let deleting_held = [KeyCode::Back, KeyCode::Delete]
+        .iter()
+        .any(|key| held_before.contains_key(key));
+    let chars_all = char_evr.iter().map(|rc| rc.char).collect::<Vec<_>>();
+    let s = chars_all
+        .iter()
+        .copied()
+        .filter(|ch| !ch.is_control())
+        .collect::<String>();
+    let mut control_chars = chars_all
+        .iter()
+        .copied()
+        .filter(|ch| ch.is_control())
+        .filter(|ch| !(deleting_held && matches!(ch, '\u{8}' | '\u{7f}')))
+        .collect::<Vec<_>>();
+    if repeated.contains(&KeyCode::Back) {
+        control_chars.push('\u{8}');
+    }
+    if repeated.contains(&KeyCode::Delete) {
+        control_chars.push('\u{7f}');
+    }
+    let shift = keyboard.any_pressed([KeyCode::LShift, KeyCode::RShift]);
+    let ctrl = keyboard.any_pressed([KeyCode::LControl, KeyCode::RControl]);
+    let moving = [
+        &keybinds.move_left,
+        &keybinds.move_right,
+        &keybinds.line_start,
+        &keybinds.line_end,
+        &keybinds.line_up,
+        &keybinds.line_down,
+    ]
+    .iter()
+    .any(|keybind| keybind.pressed(&keys));
+    for (
+        entity,
+        style,
+        mut value,
+        mut focus,
+        constrains,
+        mode,
+        paste_filter,
+        transform,
+        guard,
+        mut selection,
+        mut history,
+        mask,
+        status,
+        overtype,
+        escape,
+    ) in query.iter_mut()
+    {
+        let status = status.copied().unwrap_or_default();
+        if status == TextInputStatus::Disabled {
+            continue;
+        }
+        // read-only inputs can still move the cursor, select and copy
+        let editable = status == TextInputStatus::Enabled;
+        if focus.0.is_some() && keys.contains(&KeyCode::Escape) {
+            let escape = match escape.copied().unwrap_or_default() {
+                EscapeBehavior::ClearAndBlur if !editable => EscapeBehavior::Blur,
+                escape => escape,
+            };
+            if escape != EscapeBehavior::Ignore {
+                escape.apply(&mut value, &mut focus, selection.as_deref_mut());
+                continue;
+            }
+        }
+        if let Some(cursor) = focus.0.as_mut() {
+            let calculator = glyph_calculators.get(&fonts, &style.0.font, None);
+            if calculator.is_none() && style.0.font == Handle::default() && !*warned_no_font {
+                warn!("A text input has no font in InputTextStyle, Up and Down keys won't work");
+                *warned_no_font = true;
+            }
+            if let Some(history) = history.as_mut().filter(|_| editable) {
+                let undo = control_chars.contains(&'\u{1a}') && !shift;
+                let redo = control_chars.contains(&'\u{19}')
+                    || (control_chars.contains(&'\u{1a}') && shift);
+                if undo || redo {
+                    let restored = if undo {
+                        history.undo(&value.0)
+                    } else {
+                        history.redo(&value.0)
+                    };
+                    if let Some(restored) = restored {
+                        *cursor = (*cursor).min(restored.chars().count());
+                        value.0 = restored;
+                        if let Some(selection) = selection.as_mut() {
+                            selection.anchor = None;
+                        }
+                    }
+                    continue;
+                }
+            }
+            if control_chars.contains(&'\r') && *mode == InputMode::SingleLine {
+                submit.send(TextInputSubmit {
+                    entity,
+                    value: value.0.clone(),
+                });
+            }
+            let mut new_value = value.0.clone();
+            // the cursor is a character index, but editing works with bytes
+            let mut new_cursor = byte_index(&value.0, *cursor);
+            let mut anchor = selection
+                .as_ref()
+                .and_then(|selection| selection.anchor)
+                .map(|anchor| byte_index(&value.0, anchor));
+            if control_chars.contains(&'\r')
+                && *mode == InputMode::Multiline
+                && editable
+                && delete_selection(&mut new_value, &mut new_cursor, &mut anchor, guard).is_some()
+                && EditGuard::allows(
+                    guard,
+                    &EditOp::Insert {
+                        at: char_index(&new_value, new_cursor),
+                        text: "\n".to_string(),
+                    },
+                )
+            {
+                // new line
+                new_value.insert(new_cursor, '\n');
+                new_cursor += 1;
+            }
+            if control_chars.contains(&'\u{1}') {
+                // Ctrl-A
+                new_cursor = 0;
+            }
+            if control_chars.contains(&'\u{5}') {
+                // Ctrl-E
+                new_cursor = new_value.len();
+            }
+            let is_masked = !matches!(mask, None | Some(TextInputMask::None));
+            if (control_chars.contains(&'\u{3}') || control_chars.contains(&'\u{18}')) && !is_masked
+            {
+                // copy and cut work with the whole value if nothing is selected
+                let selected = anchor
+                    .filter(|&anchor| anchor != new_cursor)
+                    .map_or(0..new_value.len(), |anchor| {
+                        anchor.min(new_cursor)..anchor.max(new_cursor)
+                    });
+                clipboard
+                    .0
+                    .set_contents(new_value[selected.clone()].to_string());
+                if control_chars.contains(&'\u{18}') && editable {
+                    // cut
+                    anchor = Some(selected.start);
+                    new_cursor = selected.end;
+                    delete_selection(&mut new_value, &mut new_cursor, &mut anchor, guard);
+                }
+            }
+            // some platforms send \u{7f} for Ctrl-Backspace, so word deletion is told apart by keys
+            let word_backspace = ctrl && keys.contains(&KeyCode::Back);
+            let word_delete = ctrl && keys.contains(&KeyCode::Delete);
+            let backspace = control_chars.contains(&'\u{8}') || word_backspace;
+            let delete = (control_chars.contains(&'\u{7f}') && !word_backspace) || word_delete;
+            let deleted_selection = if editable && (backspace || delete) {
+                // backspace and delete only remove the selection if there is one
+                delete_selection(&mut new_value, &mut new_cursor, &mut anchor, guard) != Some(false)
+            } else {
+                false
+            };
+            if backspace && editable && new_cursor != 0 && !deleted_selection {
+                let start = if word_backspace {
+                    prev_word_boundary(&new_value, new_cursor)
+                } else {
+                    let previous = new_value[..new_cursor].chars().next_back().unwrap();
+                    new_cursor - previous.len_utf8()
+                };
+                let range = char_index(&new_value, start)..char_index(&new_value, new_cursor);
+                if EditGuard::allows(guard, &EditOp::Backspace { range }) {
+                    // backspace
+                    new_value.replace_range(start..new_cursor, "");
+                    new_cursor = start;
+                }
+            }
+            if delete && editable && new_cursor < new_value.len() && !deleted_selection {
+                let end = if word_delete {
+                    next_word_boundary(&new_value, new_cursor)
+                } else {
+                    let next = new_value[new_cursor..].chars().next().unwrap();
+                    new_cursor + next.len_utf8()
+                };
+                let range = char_index(&new_value, new_cursor)..char_index(&new_value, end);
+                if EditGuard::allows(guard, &EditOp::Delete { range }) {
+                    // delete
+                    new_value.replace_range(new_cursor..end, "");
+                }
+            }
+            // only reported once the paste is actually applied
+            let mut dropped_paste = None;
+            let pasted = if control_chars.contains(&'\u{16}') && editable {
+                clipboard.0.get_contents()
+            } else {
+                None
+            };
+            if let Some(contents) = pasted {
+                // the selection is only replaced if there is something to paste
+                if delete_selection(&mut new_value, &mut new_cursor, &mut anchor, guard).is_some() {
+                    // paste
+                    let contents = contents
+                        .chars()
+                        .filter(|&ch| keep_pasted_char(ch, *mode, paste_filter))
+                        .collect::<String>();
+                    let contents = transform_inserted(&contents, transform);
+                    let (fitting, dropped) =
+                        constrains.fit_paste(&value.0, &new_value, new_cursor, &contents);
+                    let paste = EditOp::Paste {
+                        at: char_index(&new_value, new_cursor),
+                        text: fitting.clone(),
+                    };
+                    if EditGuard::allows(guard, &paste) {
+                        new_value.insert_str(new_cursor, &fitting);
+                        new_cursor += fitting.len();
+                        dropped_paste = Some(dropped).filter(|dropped| !dropped.is_empty());
+                    }
+                }
+            }
+            let before_moving = new_cursor;
+            if keybinds.move_left.pressed(&keys) {
+                if ctrl {
+                    new_cursor = prev_word_boundary(&new_value, new_cursor);
+                } else if let Some(previous) = new_value[..new_cursor].chars().next_back() {
+                    new_cursor -= previous.len_utf8();
+                }
+            }
+            if keybinds.move_right.pressed(&keys) {
+                if ctrl {
+                    new_cursor = next_word_boundary(&new_value, new_cursor);
+                } else if let Some(next) = new_value[new_cursor..].chars().next() {
+                    new_cursor += next.len_utf8();
+                }
+            }
+
+            if keybinds.line_start.pressed(&keys) {
+                new_cursor = new_value[..new_cursor].rfind('\n').map_or(0, |i| i + 1);
+            }
+            if keybinds.line_end.pressed(&keys) {
+                new_cursor = new_value[new_cursor..]
+                    .find('\n')
+                    .map_or(new_value.len(), |i| new_cursor + i);
+            }
+
+            // moving between lines needs text measurement
+            if let Some(calculator) = calculator {
+                let scale = PxScale {
+                    x: style.0.font_size,
+                    y: style.0.font_size,
+                };
+                if keybinds.line_up.pressed(&keys) {
+                    if new_value[..new_cursor].split('\n').count() <= 1 {
+                        new_cursor = 0;
+                    } else {
+                        let mut lines_before_cursor = new_value[..new_cursor].split('\n').rev();
+                        let current_line_before_cursor = lines_before_cursor.next().unwrap();
+                        let previous_line = lines_before_cursor.next().unwrap();
+                        let target_width =
+                            text_width(current_line_before_cursor, calculator, scale);
+                        let x = closest_boundary(previous_line, calculator, scale, target_width);
+                        new_cursor = x + lines_before_cursor
+                            .map(|line| line.len() + 1)
+                            .sum::<usize>();
+                    }
+                }
+                if keybinds.line_down.pressed(&keys) {
+                    if new_value[new_cursor..].split('\n').count() <= 1 {
+                        new_cursor = new_value.len();
+                    } else {
+                        let mut lines_after_cursor = new_value[new_cursor..].split('\n');
+                        let current_line_before_cursor =
+                            new_value[..new_cursor].split('\n').next_back().unwrap();
+                        let current_line_after_cursor = lines_after_cursor.next().unwrap();
+                        let next_line = lines_after_cursor.next().unwrap();
+                        let target_width =
+                            text_width(current_line_before_cursor, calculator, scale);
+                        let x = closest_boundary(next_line, calculator, scale, target_width);
+                        new_cursor = new_cursor + current_line_after_cursor.len() + 1 + x;
+                    }
+                }
+            }
+
+            if moving {
+                if shift {
+                    anchor.get_or_insert(before_moving);
+                } else {
+                    anchor = None;
+                }
+            }
+
+            let overtype = match overtype {
+                Some(mut overtype) => {
+                    if keys.contains(&KeyCode::Insert) {
+                        overtype.0 = !overtype.0;
+                    }
+                    overtype.0
+                }
+                None => false,
+            };
+            let typed = transform_inserted(&s, transform);
+            if editable && !typed.is_empty() {
+                let deleted_selection =
+                    delete_selection(&mut new_value, &mut new_cursor, &mut anchor, guard);
+                // typing over a selection only replaces the selection
+                let overwritten = if overtype && deleted_selection == Some(false) {
+                    overtype_end(&new_value, new_cursor, typed.chars().count())
+                } else {
+                    new_cursor
+                };
+                let overwrite = EditOp::Delete {
+                    range: char_index(&new_value, new_cursor)..char_index(&new_value, overwritten),
+                };
+                if deleted_selection.is_some()
+                    && (overwritten == new_cursor || EditGuard::allows(guard, &overwrite))
+                    && EditGuard::allows(
+                        guard,
+                        &EditOp::Insert {
+                            at: char_index(&new_value, new_cursor),
+                            text: typed.clone(),
+                        },
+                    )
+                {
+                    new_value.replace_range(new_cursor..overwritten, &typed);
+                    new_cursor += typed.len();
+                }
+            }
+
+            let new_anchor = anchor
+                .filter(|&anchor| anchor != new_cursor)
+                .map(|anchor| char_index(&new_value, anchor));
+            let new_cursor = char_index(&new_value, new_cursor);
+            if value.0 != new_value {
+                if let Some(reason) = constrains.rejection(&value.0, &new_value) {
+                    rejected.send(TextInputRejected { entity, reason });
+                    continue;
+                }
+            }
+            if let Some(dropped) = dropped_paste {
+                truncated.send(TextInputTruncated { entity, dropped });
+            }
+            if let Some(selection) = selection.as_mut() {
+                if selection.anchor != new_anchor {
+                    selection.anchor = new_anchor;
+                }
+            }
+            if let Some(history) = history.as_mut() {
+                if value.0 != new_value {
+                    let typing = control_chars.is_empty() && !typed.is_empty();
+                    history.record(&value.0, typing);
+                } else if *cursor != new_cursor {
+                    // typing somewhere else starts a new undo step
+                    history.typing = false;
+                }
+            }
+            if value.0 != new_value || *cursor != new_cursor {
+                value.0 = new_value;
+                *cursor = new_cursor;
+                for (parent, mut visibility, mut timer) in cursors.iter_mut() {
+                    if parent.get() == entity {
+                        visibility.is_visible = true;
+                        timer.0.reset();
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Sent when Enter is pressed in an [`InputMode::SingleLine`] text input
+#[derive(Clone, Debug)]
+pub struct TextInputSubmit {
+    /// The text input entity
+    pub entity: Entity,
+    /// The value when Enter was pressed
+    pub value: String,
+}
+
+/// Sent when [`TextInputValue`] changes, whether by typing or from code
+#[derive(Clone, Debug)]
+pub struct TextInputChanged {
+    /// The text input entity
+    pub entity: Entity,
+    /// The new value
+    pub value: String,
+    /// The value before the change
+    pub old_value: String,
+}
+
+pub fn text_input_changed_event_system(
+    query: Query<
+        (Entity, &TextInputValue, ChangeTrackers<TextInputValue>),
+        Changed<TextInputValue>,
+    >,
+    removed: RemovedComponents<TextInputValue>,
+    mut changed: EventWriter<TextInputChanged>,
+    mut old_values: Local<HashMap<Entity, String>>,
+) {
+    for entity in removed.iter() {
+        old_values.remove(&entity);
+    }
+    for (entity, value, tracker) in query.iter() {
+        let old_value = old_values.insert(entity, value.0.clone());
+        if tracker.is_added() {
+            continue;
+        }
+        let old_value = old_value.unwrap_or_default();
+        if old_value != value.0 {
+            changed.send(TextInputChanged {
+                entity,
+                value: value.0.clone(),
+                old_value,
+            });
+        }
+    }
+}
+
+/// Sent when a text input gains focus, by a click, Tab or from code
+#[derive(Clone, Copy, Debug)]
+pub struct TextInputFocused {
+    /// The text input entity
+    pub entity: Entity,
+}
+
+/// Sent when a text input loses focus, e.g. to validate the value
+#[derive(Clone, Copy, Debug)]
+pub struct TextInputBlurred {
+    /// The text input entity
+    pub entity: Entity,
+}
+
+pub fn text_input_focus_event_system(
+    query: Query<
+        (Entity, &TextInputFocus, ChangeTrackers<TextInputFocus>),
+        Changed<TextInputFocus>,
+    >,
+    removed: RemovedComponents<TextInputFocus>,
+    mut focused: EventWriter<TextInputFocused>,
+    mut blurred: EventWriter<TextInputBlurred>,
+    mut focused_inputs: Local<HashSet<Entity>>,
+) {
+    for entity in removed.iter() {
+        focused_inputs.remove(&entity);
+    }
+    for (entity, focus, tracker) in query.iter() {
+        // the focus may be changed several times in a frame, e.g. unfocused and focused
+        // again by the same click, so only the state at the end of the frame matters
+        let was_focused = if focus.0.is_some() {
+            !focused_inputs.insert(entity)
+        } else {
+            focused_inputs.remove(&entity)
+        };
+        if tracker.is_added() || was_focused == focus.0.is_some() {
+            continue;
+        }
+        if focus.0.is_some() {
+            focused.send(TextInputFocused { entity });
+        } else {
+            blurred.send(TextInputBlurred { entity });
+        }
+    }
+}
+
+/// Send this event to clear a text input's value and unfocus it.
+/// The cursor is despawned by [`text_input_move_cursor_system`] as with any other unfocus
+#[derive(Clone, Copy, Debug)]
+pub struct ResetTextInput {
+    /// The text input entity
+    pub entity: Entity,
+}
+
+pub fn text_input_reset_system(
+    mut events: EventReader<ResetTextInput>,
+    mut query: Query<(
+        &mut TextInputValue,
+        &mut TextInputFocus,
+        Option<&mut TextInputSelection>,
+        Option<&mut TextInputHistory>,
+    )>,
+) {
+    for event in events.iter() {
+        if let Ok((mut value, mut focus, selection, history)) = query.get_mut(event.entity) {
+            value.0.clear();
+            focus.0 = None;
+            if let Some(mut selection) = selection {
+                selection.anchor = None;
+            }
+            // undo would bring back the value from before the reset
+            if let Some(mut history) = history {
+                history.clear();
+            }
+        }
+    }
+}
+
+pub fn text_input_clamp_cursor_system(
+    mut query: Query<
+        (
+            &TextInputValue,
+            &mut TextInputFocus,
+            Option<&mut TextInputSelection>,
+        ),
+        Changed<TextInputValue>,
+    >,
+) {
+    for (value, mut focus, selection) in query.iter_mut() {
+        let len = value.chars().count();
+        if focus.0 > Some(len) {
+            focus.0 = Some(len);
+        }
+        if let Some(mut selection) = selection {
+            if selection.anchor > Some(len) {
+                selection.anchor = Some(len);
+            }
+        }
+    }
+}
+
+/// Inserts dropped file paths into the focused text input like typed text: the path replaces
+/// the selection, goes through [`OnInsertTransform`], [`EditGuard`] and the constrains,
+/// and can be undone
+pub fn text_input_file_drop_system(
+    mut events: EventReader<FileDragAndDrop>,
+    mut query: Query<
+        (
+            Entity,
+            &mut TextInputValue,
+            &mut TextInputFocus,
+            &TextInputConstrains,
+            Option<&OnInsertTransform>,
+            Option<&EditGuard>,
+            Option<&mut TextInputSelection>,
+            Option<&mut TextInputHistory>,
+            Option<&TextInputStatus>,
+        ),
+        Without<InputLocked>,
+    >,
+    mut rejected: EventWriter<TextInputRejected>,
+) {
+    for event in events.iter() {
+        if let FileDragAndDrop::DroppedFile { path_buf, .. } = event {
+            let path = path_buf.to_string_lossy();
+            for (
+                entity,
+                mut value,
+                mut focus,
+                constrains,
+                transform,
+                guard,
+                mut selection,
+                history,
+                status,
+            ) in query.iter_mut()
+            {
+                if matches!(
+                    status,
+                    Some(TextInputStatus::ReadOnly | TextInputStatus::Disabled)
+                ) {
+                    continue;
+                }
+                let cursor = match focus.0 {
+                    Some(cursor) => cursor,
+                    None => continue,
+                };
+                let mut new_value = value.0.clone();
+                let mut new_cursor = byte_index(&value.0, cursor);
+                let mut anchor = selection
+                    .as_ref()
+                    .and_then(|selection| selection.anchor)
+                    .map(|anchor| byte_index(&value.0, anchor));
+                if delete_selection(&mut new_value, &mut new_cursor, &mut anchor, guard).is_none() {
+                    continue;
+                }
+                let text = transform_inserted(&path, transform);
+                let op = EditOp::Insert {
+                    at: char_index(&new_value, new_cursor),
+                    text: text.clone(),
+                };
+                if !EditGuard::allows(guard, &op) {
+                    continue;
+                }
+                new_value.insert_str(new_cursor, &text);
+                new_cursor += text.len();
+                if let Some(reason) = constrains.rejection(&value.0, &new_value) {
+                    rejected.send(TextInputRejected { entity, reason });
+                    continue;
+                }
+                if let Some(mut history) = history {
+                    history.record(&value.0, false);
+                }
+                if let Some(selection) = selection.as_mut() {
+                    if selection.anchor.is_some() {
+                        selection.anchor = None;
+                    }
+                }
+                focus.0 = Some(char_index(&new_value, new_cursor));
+                value.0 = new_value;
+            }
+        }
+    }
+}
+
+/// Removes the selected text, if any, and moves the cursor to where it started.
+/// Returns `Some(true)` if the selection was removed, `Some(false)` if nothing was selected,
+/// and `None` if [`EditGuard`] cancelled the removal
+fn delete_selection(
+    value: &mut String,
+    cursor: &mut usize,
+    anchor: &mut Option<usize>,
+    guard: Option<&EditGuard>,
+) -> Option<bool> {
+    let range = match anchor.filter(|&anchor| anchor != *cursor) {
+        Some(anchor) => anchor.min(*cursor)..anchor.max(*cursor),
+        None => {
+            *anchor = None;
+            return Some(false);
+        }
+    };
+    let op = EditOp::Delete {
+        range: char_index(value, range.start)..char_index(value, range.end),
+    };
+    if !EditGuard::allows(guard, &op) {
+        return None;
+    }
+    value.replace_range(range.clone(), "");
+    *cursor = range.start;
+    *anchor = None;
+    Some(true)
+}
+
+/// Byte offset of the character at `char_index`, or the length of `text` if it's past the end
+fn byte_index(text: &str, char_index: usize) -> usize {
+    text.char_indices()
+        .nth(char_index)
+        .map_or(text.len(), |(i, _)| i)
+}
+
+/// Character index of the character at byte offset `byte_index`
+fn char_index(text: &str, byte_index: usize) -> usize {
+    text[..byte_index].chars().count()
+}
+
+/// Byte offset of the start of the word before `byte_index`, skipping whitespace in between
+fn prev_word_boundary(text: &str, byte_index: usize) -> usize {
+    let before = text[..byte_index].trim_end();
+    before
+        .rfind(char::is_whitespace)
+        .map_or(0, |i| i + before[i..].chars().next().unwrap().len_utf8())
+}
+
+/// Byte offset of the end of the word after `byte_index`, skipping whitespace in between
+fn next_word_boundary(text: &str, byte_index: usize) -> usize {
+    let after = text[byte_index..].trim_start();
+    let start = text.len() - after.len();
+    after
+        .find(char::is_whitespace)
+        .map_or(text.len(), |i| start + i)
+}
+
+/// Byte offsets of every character in `text`, and the length of `text`
+fn char_boundaries(text: &str) -> impl Iterator<Item = usize> + '_ {
+    text.char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(text.len()))
+}
+
+pub fn text_input_font_decrease_system(
+    fonts: Res<Assets<Font>>,
+    mut glyph_calculators: ResMut<GlyphCalculatorCache>,
+    query: Query<(
+        Entity,
+        &InputTextStyle,
+        &TextInputTargetSize,
+        &TextInputValue,
+        Option<&TextInputMask>,
+    )>,
+    mut text: Query<(&Parent, &mut Text), With<TextInputInner>>,
+) {
+    for (entity, style, target_size, value, mask) in query.iter() {
+        let value = masked(&value.0, mask);
+        if target_size.size.is_some() {
+            for mut text in text
+                .iter_mut()
+                .filter(|(parent, _)| parent.get() == entity)
+                .map(|(_, text)| text)
+            {
+                let calculator =
+                    match glyph_calculators.get(&fonts, &text.sections[0].style.font, None) {
+                        Some(calculator) => calculator,
+                        None => continue,
+                    };
+                let scale = PxScale {
+                    x: style.0.font_size,
+                    y: style.0.font_size,
+                };
+
+                let width = text_width(&value, calculator, scale);
+                // a trailing new line starts an empty line that is displayed too
+                let height =
+                    value.split('\n').count() as f32 * line_height(&calculator.fonts()[0], scale);
+                let font_size =
+                    target_size.fit_font_size(style.0.font_size, Vec2::new(width, height));
+                if (text.sections[0].style.font_size - font_size).abs() > f32::EPSILON {
+                    for section in &mut text.sections {
+                        section.style.font_size = font_size;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Makes the text input node as wide as its text, the cursor, padding and border,
+/// but not narrower than `min` and not wider than `max`
+#[derive(Component, Clone, Copy, Debug)]
+pub struct AutoWidth {
+    /// Minimum width of the node
+    pub min: Val,
+    /// Maximum width of the node
+    pub max: Val,
+}
+
+pub fn text_input_auto_width_system(
+    fonts: Res<Assets<Font>>,
+    mut glyph_calculators: ResMut<GlyphCalculatorCache>,
+    mut query: Query<
+        (
+            &InputTextStyle,
+            &TextInputValue,
+            Option<&TextInputMask>,
+            &AutoWidth,
+            &mut Style,
+        ),
+        Or<(
+            Changed<TextInputValue>,
+            Changed<TextInputMask>,
+            Changed<AutoWidth>,
+        )>,
+    >,
+) {
+    for (text_style, value, mask, auto_width, mut style) in query.iter_mut() {
+        let calculator = match glyph_calculators.get(&fonts, &text_style.0.font, None) {
+            Some(calculator) => calculator,
+            None => continue,
+        };
+        let scale = PxScale {
+            x: text_style.0.font_size,
+            y: text_style.0.font_size,
+        };
+        let text = masked(&value.0, mask)
+            .split('\n')
+            .map(|line| text_width(line, calculator, scale))
+            .fold(0.0, f32::max);
+        let cursor = text_style.0.font_size / 12.0;
+        let insets = [
+            style.padding.left,
+            style.padding.right,
+            style.border.left,
+            style.border.right,
+        ]
+        .into_iter()
+        .map(|val| match val {
+            Val::Px(px) => px,
+            _ => 0.0,
+        })
+        .sum::<f32>();
+        style.size.width = Val::Px(text + cursor + insets);
+        style.min_size.width = auto_width.min;
+        style.max_size.width = auto_width.max;
+    }
+}
+
+/// Makes a multiline text input node as tall as its lines, padding and border,
+/// but not shorter than `min` and not taller than `max`, like a chat box.
+/// Add [`TextInputScroll`] to scroll the lines that don't fit into `max`
+#[derive(Component, Clone, Copy, Debug)]
+pub struct TextInputAutoGrow {
+    /// Minimum height of the node
+    pub min: Val,
+    /// Maximum height of the node
+    pub max: Val,
+}
+
+pub fn text_input_auto_grow_system(
+    fonts: Res<Assets<Font>>,
+    mut query: Query<
+        (
+            &InputTextStyle,
+            &TextInputValue,
+            &TextInputAutoGrow,
+            &mut Style,
+        ),
+        Or<(Changed<TextInputValue>, Changed<TextInputAutoGrow>)>,
+    >,
+) {
+    for (text_style, value, auto_grow, mut style) in query.iter_mut() {
+        let font = match fonts.get(&text_style.0.font) {
+            Some(font) => font.font.clone(),
+            None => continue,
+        };
+        let scale = PxScale {
+            x: text_style.0.font_size,
+            y: text_style.0.font_size,
+        };
+        let lines = value.0.split('\n').count() as f32;
+        let insets = [
+            style.padding.top,
+            style.padding.bottom,
+            style.border.top,
+            style.border.bottom,
+        ]
+        .into_iter()
+        .map(|val| match val {
+            Val::Px(px) => px,
+            _ => 0.0,
+        })
+        .sum::<f32>();
+        style.size.height = Val::Px(lines * line_height(&font, scale) + insets);
+        style.min_size.height = auto_grow.min;
+        style.max_size.height = auto_grow.max;
+    }
+}
+
+/// Shows the number of characters in the bottom right corner of the text input, e.g. "120 / 280".
+/// The text turns red when there are more than `max` characters.
+/// It's updated by [`SystemLabels::TextInputCounter`](crate::SystemLabels::TextInputCounter)
+#[derive(Component, Clone, Debug, Default)]
+pub struct TextInputCounter {
+    /// The limit displayed after the count
+    pub max: Option<usize>,
+    /// Style of the counter text
+    pub style: TextStyle,
+}
+
+/// The text node spawned for [`TextInputCounter`]
+#[derive(Component)]
+pub struct TextInputCounterText;
+
+fn counter_text(counter: &TextInputCounter, value: &str) -> Text {
+    let count = value.chars().count();
+    let mut style = counter.style.clone();
+    let value = match counter.max {
+        Some(max) => {
+            if count > max {
+                style.color = Color::RED;
+            }
+            format!("{} / {}", count, max)
+        }
+        None => count.to_string(),
+    };
+    Text::from_section(value, style)
+}
+
+pub fn text_input_counter_create_system(
+    mut commands: Commands,
+    query: Query<(Entity, &TextInputCounter, &TextInputValue), Added<TextInputCounter>>,
+) {
+    for (entity, counter, value) in query.iter() {
+        commands.entity(entity).with_children(|parent| {
+            parent
+                .spawn_bundle(TextBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        position: UiRect {
+                            right: Val::Px(0.0),
+                            bottom: Val::Px(0.0),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    text: counter_text(counter, &value.0),
+                    ..Default::default()
+                })
+                .insert(TextInputCounterText);
+        });
+    }
+}
+
+pub fn text_input_counter_system(
+    query: Query<
+        (&TextInputCounter, &TextInputValue),
+        Or<(Changed<TextInputValue>, Changed<TextInputCounter>)>,
+    >,
+    mut query_text: Query<(&Parent, &mut Text), With<TextInputCounterText>>,
+) {
+    for (parent, mut text) in query_text.iter_mut() {
+        if let Ok((counter, value)) = query.get(parent.get()) {
+            *text = counter_text(counter, &value.0);
+        }
+    }
+}
+
+/// Fonts used for characters that [`InputTextStyle`]'s font doesn't have, in order of priority.
+/// The value is split into text sections with these fonts, and the cursor is measured the same way
+#[derive(Component, Clone, Debug, Default)]
+pub struct FallbackFonts(pub Vec<Handle<Font>>);
+
+/// The main font followed by the fallback fonts. Fallback fonts that aren't loaded yet
+/// are replaced with the main font, so indices always match [`FallbackFonts`]
+fn font_list(
+    fonts: &Assets<Font>,
+    main: &Handle<Font>,
+    fallback: Option<&FallbackFonts>,
+) -> Option<Vec<FontArc>> {
+    let main = fonts.get(main)?.font.clone();
+    let mut font_list = vec![main.clone()];
+    if let Some(fallback) = fallback {
+        font_list.extend(fallback.0.iter().map(|handle| {
+            fonts
+                .get(handle)
+                .map_or_else(|| main.clone(), |font| font.font.clone())
+        }));
+    }
+    Some(font_list)
+}
+
+/// Glyph calculators that text inputs are measured with, by their main font and
+/// [`FallbackFonts`]. A calculator is built the first time its fonts are used instead of
+/// for every measurement, and is dropped when any of its fonts is loaded, changed or removed
+#[derive(Default)]
+pub struct GlyphCalculatorCache(HashMap<Vec<Handle<Font>>, GlyphCalculator<FontArc>>);
+
+impl GlyphCalculatorCache {
+    /// The calculator for `main` followed by `fallback` fonts, see [`font_list`].
+    /// Returns `None` if the main font isn't loaded yet
+    fn get(
+        &mut self,
+        fonts: &Assets<Font>,
+        main: &Handle<Font>,
+        fallback: Option<&FallbackFonts>,
+    ) -> Option<&GlyphCalculator<FontArc>> {
+        let handles = std::iter::once(main)
+            .chain(fallback.into_iter().flat_map(|fallback| &fallback.0))
+            .map(Handle::clone_weak)
+            .collect::<Vec<_>>();
+        if !self.0.contains_key(&handles) {
+            let font_list = font_list(fonts, main, fallback)?;
+            self.0.insert(
+                handles.clone(),
+                GlyphCalculatorBuilder::using_fonts(font_list).build(),
+            );
+        }
+        self.0.get(&handles)
+    }
+}
+
+pub fn glyph_calculator_cache_system(
+    mut glyph_calculators: ResMut<GlyphCalculatorCache>,
+    mut font_events: EventReader<AssetEvent<Font>>,
+) {
+    for event in font_events.iter() {
+        let handle = match event {
+            AssetEvent::Created { handle }
+            | AssetEvent::Modified { handle }
+            | AssetEvent::Removed { handle } => handle,
+        };
+        // calculators built before a fallback font was loaded use the main font in its place
+        glyph_calculators
+            .0
+            .retain(|handles, _| !handles.contains(handle));
+    }
+}
+
+/// Splits `text` into runs of characters that can be displayed with the same font from `font_list`.
+/// Whitespace and characters that no font has stay in the current run
+fn font_runs<'a>(text: &'a str, font_list: &[FontArc]) -> Vec<(usize, &'a str)> {
+    runs_by_font(text, |ch| {
+        font_list.iter().position(|font| font.glyph_id(ch).0 != 0)
+    })
+}
+
+/// [`font_runs`] with the font of each character picked by `font_for`
+fn runs_by_font(text: &str, font_for: impl Fn(char) -> Option<usize>) -> Vec<(usize, &str)> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    let mut current = 0;
+    for (index, ch) in text.char_indices() {
+        if ch.is_whitespace() || ch.is_control() {
+            continue;
+        }
+        let font = match font_for(ch) {
+            Some(font) => font,
+            None => continue,
+        };
+        if font != current && index > start {
+            runs.push((current, &text[start..index]));
+            start = index;
+        }
+        current = font;
+    }
+    runs.push((current, &text[start..]));
+    runs
+}
+
+/// Width of `text`, with each run of characters measured with the font from `calculator`
+/// that [`font_runs`] picks for it
+fn text_width_with_fallback(
+    text: &str,
+    calculator: &GlyphCalculator<FontArc>,
+    scale: PxScale,
+) -> f32 {
+    let section = font_runs(text, calculator.fonts()).into_iter().fold(
+        Section::default(),
+        |section, (font, run)| {
+            section.add_text(
+                glyph_brush::Text::new(run)
+                    .with_scale(scale)
+                    .with_font_id(FontId(font)),
+            )
+        },
+    );
+    calculator
+        .cache_scope()
+        .glyph_bounds(section)
+        .map(|rect| rect.width())
+        .unwrap_or_default()
+}
+
+/// Width of every prefix of `line` that ends at a character boundary, as measured by
+/// [`text_width_with_fallback`], but laid out once instead of once per prefix.
+/// `line` must not contain new lines
+fn prefix_widths(
+    line: &str,
+    calculator: &GlyphCalculator<FontArc>,
+    scale: PxScale,
+) -> Vec<(usize, f32)> {
+    let font_list = calculator.fonts();
+    // runs follow each other, so each one starts where the previous one ends
+    let mut run_starts = Vec::new();
+    let mut run_end = 0;
+    let section =
+        font_runs(line, font_list)
+            .into_iter()
+            .fold(Section::default(), |section, (font, run)| {
+                run_starts.push(run_end);
+                run_end += run.len();
+                section.add_text(
+                    glyph_brush::Text::new(run)
+                        .with_scale(scale)
+                        .with_font_id(FontId(font)),
+                )
+            });
+    // the same bounds that glyph_bounds unites, with the byte index in `line`
+    let mut glyphs = calculator
+        .cache_scope()
+        .glyphs(section)
+        .map(|section_glyph| {
+            let font = font_list[section_glyph.font_id.0].as_scaled(section_glyph.glyph.scale);
+            let id = section_glyph.glyph.id;
+            let x = section_glyph.glyph.position.x;
+            (
+                run_starts[section_glyph.section_index] + section_glyph.byte_index,
+                x - font.h_side_bearing(id),
+                x + font.h_advance(id),
+            )
+        })
+        .collect::<Vec<_>>();
+    glyphs.sort_by_key(|(index, ..)| *index);
+
+    let mut glyphs = glyphs.into_iter().peekable();
+    let mut bounds: Option<(f32, f32)> = None;
+    char_boundaries(line)
+        .map(|boundary| {
+            while let Some((_, min, max)) = glyphs.next_if(|(index, ..)| *index < boundary) {
+                bounds = Some(bounds.map_or((min, max), |(lo, hi)| (lo.min(min), hi.max(max))));
+            }
+            // glyph_bounds caps the bounds to the layout, which starts at 0
+            (boundary, bounds.map_or(0.0, |(lo, hi)| hi - lo.max(0.0)))
+        })
+        .collect()
+}
+
+/// Byte index of the character boundary in `line` that is closest to `x`
+fn closest_boundary(
+    line: &str,
+    calculator: &GlyphCalculator<FontArc>,
+    scale: PxScale,
+    x: f32,
+) -> usize {
+    prefix_widths(line, calculator, scale)
+        .into_iter()
+        .min_by(|(_, width1), (_, width2)| {
+            // width is never f32::NAN, so unwrap is safe
+            (width1 - x).abs().partial_cmp(&(width2 - x).abs()).unwrap()
+        })
+        .unwrap()
+        .0
+}
+
+/// Distance between lines, the same that glyph_brush uses when laying out bevy's text
+fn line_height(font: &FontArc, scale: PxScale) -> f32 {
+    let scaled_font = font.as_scaled(scale);
+    scaled_font.height() + scaled_font.line_gap()
+}
+
+/// Character index of the boundary closest to `local_pos`, which is relative to the top left
+/// corner of a text node of `size`, both in the same units as `scale`. The lines are placed
+/// in the node by `alignment`, and the line is picked by the vertical offset
+fn cursor_index_at(
+    value: &str,
+    calculator: &GlyphCalculator<FontArc>,
+    scale: PxScale,
+    alignment: &TextAlignment,
+    size: Vec2,
+    local_pos: Vec2,
+) -> usize {
+    let lines = value.split('\n').collect::<Vec<_>>();
+    let line_height = line_height(&calculator.fonts()[0], scale);
+    let top = line_top_in_view(value, 0, text_anchor(alignment), line_height, size.y, 0.0);
+    let line_index = (((local_pos.y - top) / line_height).max(0.0) as usize).min(lines.len() - 1);
+    let line = lines[line_index];
+    let left = line_left_in_view(
+        text_width_with_fallback(line, calculator, scale),
+        text_horizontal_anchor(alignment),
+        size.x,
+    );
+    let x = closest_boundary(line, calculator, scale, local_pos.x - left);
+    lines[..line_index]
+        .iter()
+        .map(|line| line.chars().count() + 1)
+        .sum::<usize>()
+        + char_index(line, x)
+}
+
+/// Width of `text` measured with the main font of `calculator`
+fn text_width(text: &str, calculator: &GlyphCalculator<FontArc>, scale: PxScale) -> f32 {
+    calculator
+        .cache_scope()
+        .glyph_bounds(Section::default().add_text(glyph_brush::Text::new(text).with_scale(scale)))
+        .map(|rect| rect.width())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::event::ManualEventReader;
+    use bevy::ecs::system::CommandQueue;
+    use bevy::utils::Instant;
+
+    use super::*;
+
+    #[test]
+    fn multiline_mode_removes_default_newline_constrain() {
+        let mut app = App::new();
+        app.add_system(text_input_mode_system);
+        let multiline = app
+            .world
+            .spawn()
+            .insert(InputMode::Multiline)
+            .insert(TextInputConstrains::default())
+            .id();
+        let single_line = app
+            .world
+            .spawn()
+            .insert(InputMode::SingleLine)
+            .insert(TextInputConstrains::default())
+            .id();
+        app.update();
+
+        let constrains = app.world.get::<TextInputConstrains>(multiline).unwrap();
+        assert_eq!(constrains.rejection("hello", "hello\n"), None);
+        let constrains = app.world.get::<TextInputConstrains>(single_line).unwrap();
+        assert!(constrains.rejection("hello", "hello\n").is_some());
+    }
+
+    #[test]
+    fn pasted_new_lines_depend_on_mode() {
+        assert!(keep_pasted_char('\n', InputMode::Multiline, None));
+        assert!(!keep_pasted_char('\n', InputMode::SingleLine, None));
+        assert!(keep_pasted_char('a', InputMode::SingleLine, None));
+    }
+
+    #[test]
+    fn typing_is_undone_in_one_step() {
+        let mut history = TextInputHistory::default();
+        let mut value = String::new();
+        for ch in "hello".chars() {
+            history.record(&value, true);
+            value.push(ch);
+        }
+        assert_eq!(history.undo(&value).as_deref(), Some(""));
+        assert_eq!(history.undo(""), None);
+        assert_eq!(history.redo("").as_deref(), Some("hello"));
+    }
+
+    #[test]
+    fn reset_clears_history() {
+        let mut app = App::new();
+        app.add_event::<ResetTextInput>()
+            .add_system(text_input_reset_system);
+        let mut history = TextInputHistory::default();
+        history.record("", true);
+        let entity = app
+            .world
+            .spawn()
+            .insert(TextInputValue("hello".to_string()))
+            .insert(TextInputFocus(Some(5)))
+            .insert(history)
+            .id();
+        app.world.send_event(ResetTextInput { entity });
+        app.update();
+
+        assert_eq!(app.world.get::<TextInputValue>(entity).unwrap().0, "");
+        let mut history = app.world.get_mut::<TextInputHistory>(entity).unwrap();
+        assert_eq!(history.undo(""), None);
+    }
+
+    #[test]
+    fn clear_text_input_clears_history() {
+        let mut world = World::new();
+        let mut history = TextInputHistory::default();
+        history.record("", true);
+        let entity = world
+            .spawn()
+            .insert(TextInputValue("hello".to_string()))
+            .insert(TextInputFocus(Some(5)))
+            .insert(history)
+            .id();
+        let mut queue = CommandQueue::default();
+        Commands::new(&mut queue, &world).clear_text_input(entity);
+        queue.apply(&mut world);
+
+        assert_eq!(world.get::<TextInputValue>(entity).unwrap().0, "");
+        let mut history = world.get_mut::<TextInputHistory>(entity).unwrap();
+        assert_eq!(history.undo(""), None);
+    }
+
+    #[test]
+    fn state_round_trips_selection() {
+        let mut value = TextInputValue("hello world".to_string());
+        let mut focus = TextInputFocus(Some(11));
+        let mut selection = TextInputSelection { anchor: Some(6) };
+        let state = TextInputState::capture(&value, &focus, Some(&selection));
+        assert_eq!(state.selection, Some((6, 11)));
+
+        value.0.clear();
+        focus.0 = None;
+        selection.anchor = None;
+        state.apply(&mut value, &mut focus, Some(&mut selection));
+        assert_eq!(value.0, "hello world");
+        assert_eq!(focus.0, Some(11));
+        assert_eq!(selection.anchor, Some(6));
+    }
+
+    #[test]
+    fn state_drops_selection_without_cursor() {
+        let state = TextInputState {
+            value: "hi".to_string(),
+            cursor: None,
+            selection: None,
+        };
+        let mut value = TextInputValue::default();
+        let mut focus = TextInputFocus(Some(0));
+        let mut selection = TextInputSelection { anchor: Some(0) };
+        state.apply(&mut value, &mut focus, Some(&mut selection));
+        assert_eq!(focus.0, None);
+        assert_eq!(selection.anchor, None);
+    }
+
+    #[test]
+    fn fit_paste_truncates_long_paste() {
+        let constrains = TextInputConstrains::default().with(DefaultConstrains::MaxLength(1000));
+        let pasted = "x".repeat(10_000);
+        let (kept, dropped) = constrains.fit_paste("ab", "ab", 1, &pasted);
+        assert_eq!(kept.len(), 998);
+        assert_eq!(dropped.len(), 9_002);
+    }
+
+    #[test]
+    fn max_chars_counts_characters_not_bytes() {
+        let constrains = TextInputConstrains::default().with(DefaultConstrains::MaxChars(4));
+        assert!(constrains.test("caf", "café"));
+        assert!(!constrains.test("café", "cafés"));
+        let (kept, dropped) = constrains.fit_paste("", "", 0, "éééééé");
+        assert_eq!(kept, "éééé");
+        assert_eq!(dropped, "éé");
+    }
+
+    #[test]
+    fn fit_paste_filters_then_truncates() {
+        let constrains = TextInputConstrains::default()
+            .with(DefaultConstrains::AllowedCharacters(('0'..='9').collect()))
+            .with(DefaultConstrains::MaxLength(4));
+        let (kept, dropped) = constrains.fit_paste("1", "1", 1, "+2 (34) 56");
+        assert_eq!(kept, "234");
+        assert_eq!(dropped, "+ () 56");
+    }
+
+    #[test]
+    fn fit_paste_keeps_fitting_paste() {
+        let constrains = TextInputConstrains::default();
+        let (kept, dropped) = constrains.fit_paste("", "ac", 1, "b");
+        assert_eq!(kept, "b");
+        assert_eq!(dropped, "");
+    }
+
+    #[test]
+    fn placeholder_is_laid_out_like_typed_text() {
+        let mut app = App::new();
+        app.add_system(text_input_create_system);
+        let alignment = TextAlignment {
+            vertical: VerticalAlign::Center,
+            horizontal: HorizontalAlign::Left,
+        };
+        let centered = TextAlignment {
+            vertical: VerticalAlign::Center,
+            horizontal: HorizontalAlign::Center,
+        };
+        let spawn = |app: &mut App, placeholder_alignment: Option<TextAlignment>| {
+            let mut entity = app.world.spawn();
+            entity
+                .insert(PlaceholderText(
+                    Text::from_section("Type here", TextStyle::default()).with_alignment(centered),
+                ))
+                .insert(InputTextStyle::default())
+                .insert(InputTextAlignment(alignment))
+                .insert(TextInputValue::default())
+                .insert(Style {
+                    padding: UiRect::all(Val::Px(4.0)),
+                    border: UiRect::all(Val::Px(1.0)),
+                    ..Default::default()
+                });
+            if let Some(placeholder_alignment) = placeholder_alignment {
+                entity.insert(PlaceholderAlignment(placeholder_alignment));
+            }
+            entity.id()
+        };
+        let inherited = spawn(&mut app, None);
+        let overridden = spawn(&mut app, Some(centered));
+        app.update();
+        let text_nodes = |app: &mut App, input: Entity, placeholder: bool| {
+            let mut query = app
+                .world
+                .query::<(&Parent, &Style, &Text, Option<&TextInputPlaceholder>)>();
+            query
+                .iter(&app.world)
+                .find(|(parent, .., marker)| {
+                    parent.get() == input && marker.is_some() == placeholder
+                })
+                .map(|(_, style, text, _)| (style.position, text.alignment))
+                .unwrap()
+        };
+
+        // the first typed character starts where the placeholder does
+        let (placeholder_position, placeholder_alignment) = text_nodes(&mut app, inherited, true);
+        let (value_position, value_alignment) = text_nodes(&mut app, inherited, false);
+        assert_eq!(placeholder_position, value_position);
+        assert_eq!(placeholder_position.left, Val::Px(5.0));
+        assert_eq!(
+            (
+                placeholder_alignment.horizontal,
+                placeholder_alignment.vertical
+            ),
+            (value_alignment.horizontal, value_alignment.vertical)
+        );
+
+        let (_, placeholder_alignment) = text_nodes(&mut app, overridden, true);
+        assert_eq!(placeholder_alignment.horizontal, HorizontalAlign::Center);
+    }
+
+    #[test]
+    fn inner_text_position_adds_padding_and_border() {
+        let style = Style {
+            padding: UiRect {
+                left: Val::Px(4.0),
+                right: Val::Px(6.0),
+                top: Val::Percent(10.0),
+                bottom: Val::Undefined,
+            },
+            border: UiRect {
+                left: Val::Px(1.0),
+                right: Val::Percent(5.0),
+                top: Val::Percent(2.0),
+                bottom: Val::Auto,
+            },
+            ..Default::default()
+        };
+        let position = inner_text_position(&style);
+        assert_eq!(position.left, Val::Px(5.0));
+        // mixed units keep the pixels
+        assert_eq!(position.right, Val::Px(6.0));
+        assert_eq!(position.top, Val::Percent(12.0));
+        assert_eq!(position.bottom, Val::Px(0.0));
+    }
+
+    #[test]
+    fn add_vals_treats_undefined_as_zero() {
+        assert_eq!(add_vals(Val::Undefined, Val::Px(3.0)), Val::Px(3.0));
+        assert_eq!(add_vals(Val::Percent(3.0), Val::Auto), Val::Percent(3.0));
+    }
+
+    fn drop_file(app: &mut App, path: &str) {
+        app.world.send_event(FileDragAndDrop::DroppedFile {
+            id: bevy::window::WindowId::primary(),
+            path_buf: path.into(),
+        });
+        app.update();
+    }
+
+    #[test]
+    fn dropped_file_replaces_selection() {
+        let mut app = App::new();
+        app.add_event::<FileDragAndDrop>()
+            .add_event::<TextInputRejected>()
+            .add_system(text_input_file_drop_system);
+        let entity = app
+            .world
+            .spawn()
+            .insert(TextInputValue("a bc".to_string()))
+            .insert(TextInputFocus(Some(4)))
+            .insert(TextInputSelection { anchor: Some(2) })
+            .insert(TextInputConstrains::default())
+            .insert(TextInputHistory::default())
+            .id();
+        drop_file(&mut app, "/tmp/x");
+
+        assert_eq!(
+            app.world.get::<TextInputValue>(entity).unwrap().0,
+            "a /tmp/x"
+        );
+        assert_eq!(app.world.get::<TextInputFocus>(entity).unwrap().0, Some(8));
+        assert_eq!(
+            app.world.get::<TextInputSelection>(entity).unwrap().anchor,
+            None
+        );
+        let mut history = app.world.get_mut::<TextInputHistory>(entity).unwrap();
+        assert_eq!(history.undo("a /tmp/x").as_deref(), Some("a bc"));
+    }
+
+    #[test]
+    fn rejected_file_drop_is_reported() {
+        let mut app = App::new();
+        app.add_event::<FileDragAndDrop>()
+            .add_event::<TextInputRejected>()
+            .add_system(text_input_file_drop_system);
+        let entity = app
+            .world
+            .spawn()
+            .insert(TextInputValue("ab".to_string()))
+            .insert(TextInputFocus(Some(2)))
+            .insert(TextInputConstrains::default().with(DefaultConstrains::MaxLength(4)))
+            .id();
+        drop_file(&mut app, "/tmp/x");
+
+        assert_eq!(app.world.get::<TextInputValue>(entity).unwrap().0, "ab");
+        let events = app.world.resource::<Events<TextInputRejected>>();
+        let rejected = events
+            .get_reader()
+            .iter(events)
+            .map(|event| event.entity)
+            .collect::<Vec<_>>();
+        assert_eq!(rejected, [entity]);
+    }
+
+    fn fira_sans() -> GlyphCalculator<FontArc> {
+        let font = FontArc::try_from_slice(include_bytes!("../../assets/fonts/FiraSans-Bold.ttf"))
+            .unwrap();
+        GlyphCalculatorBuilder::using_font(font).build()
+    }
+
+    #[test]
+    fn line_height_matches_glyph_brush_layout() {
+        let calculator = fira_sans();
+        let scale = PxScale::from(24.0);
+        let section = Section::default().add_text(glyph_brush::Text::new("H\nH").with_scale(scale));
+        let tops = calculator
+            .cache_scope()
+            .glyphs(section)
+            .map(|glyph| glyph.glyph.position.y)
+            .collect::<Vec<_>>();
+        assert_eq!(tops.len(), 2);
+        let expected = line_height(&calculator.fonts()[0], scale);
+        assert!((tops[1] - tops[0] - expected).abs() < 0.01);
+    }
+
+    #[test]
+    fn cursor_measured_in_physical_pixels_matches_logical_width() {
+        let calculator = fira_sans();
+        let scale_factor = 2.0;
+        let logical = text_width_with_fallback("hello", &calculator, PxScale::from(20.0));
+        let physical =
+            text_width_with_fallback("hello", &calculator, PxScale::from(20.0 * scale_factor));
+        assert!((physical / scale_factor - logical).abs() < 1.0);
+    }
+
+    #[test]
+    fn emoji_runs_use_fallback_font() {
+        let font_for = |ch: char| match ch {
+            '😀' => Some(1),
+            ch if ch.is_ascii() => Some(0),
+            _ => None,
+        };
+        assert_eq!(
+            runs_by_font("hi 😀😀 there", font_for),
+            [(0, "hi "), (1, "😀😀 "), (0, "there")]
+        );
+        assert_eq!(runs_by_font("😀a", font_for), [(1, "😀"), (0, "a")]);
+        // characters no font has stay in the current run
+        assert_eq!(runs_by_font("😀ü😀", font_for), [(1, "😀ü😀")]);
+    }
+
+    #[test]
+    fn emoji_without_fallback_stays_in_main_font_run() {
+        let font_list = [fira_sans().fonts()[0].clone()];
+        assert_eq!(font_runs("a😀b", &font_list), [(0, "a😀b")]);
+    }
+
+    #[test]
+    fn prefix_widths_match_measuring_each_prefix() {
+        let calculator = fira_sans();
+        let scale = PxScale::from(18.0);
+        for line in ["", "hello", "  spaced  out ", "Wärme & Tür", "a😀b"] {
+            let widths = prefix_widths(line, &calculator, scale);
+            assert_eq!(widths.len(), line.chars().count() + 1);
+            for (index, width) in widths {
+                let expected = text_width_with_fallback(&line[..index], &calculator, scale);
+                assert!(
+                    (width - expected).abs() < 0.01,
+                    "{line:?} up to {index}: {width} != {expected}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn prefix_widths_of_long_line_match_measuring_each_prefix() {
+        let calculator = fira_sans();
+        let scale = PxScale::from(18.0);
+        let line = "The quick brown fox jumps over the lazy dög. ".repeat(45);
+        let line = &line[..line.char_indices().nth(2000).unwrap().0];
+        let widths = prefix_widths(line, &calculator, scale);
+        assert_eq!(widths.len(), 2001);
+        for (index, width) in widths {
+            let expected = text_width_with_fallback(&line[..index], &calculator, scale);
+            assert!(
+                (width - expected).abs() < 0.01,
+                "up to {index}: {width} != {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn glyph_calculator_cache_drops_calculators_when_fonts_change() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(bevy::asset::AssetPlugin)
+            .add_asset::<Font>()
+            .init_resource::<GlyphCalculatorCache>()
+            .add_system(glyph_calculator_cache_system);
+        let font = include_bytes!("../../assets/fonts/FiraSans-Bold.ttf").to_vec();
+        let main = app
+            .world
+            .resource_mut::<Assets<Font>>()
+            .add(Font::try_from_bytes(font).unwrap());
+        let missing = Handle::<Font>::weak(bevy::asset::HandleId::random::<Font>());
+        let fallback = FallbackFonts(vec![missing.clone()]);
+
+        app.world
+            .resource_scope(|world, mut cache: Mut<GlyphCalculatorCache>| {
+                let fonts = world.resource::<Assets<Font>>();
+                assert!(cache.get(fonts, &missing, None).is_none());
+                // the missing fallback font is replaced with the main font
+                let calculator = cache.get(fonts, &main, Some(&fallback)).unwrap();
+                assert_eq!(calculator.fonts().len(), 2);
+                assert!(cache.get(fonts, &main, None).is_some());
+                assert_eq!(cache.0.len(), 2);
+            });
+
+        // only the calculator that uses the loaded font is rebuilt
+        app.world
+            .send_event(AssetEvent::Created { handle: missing });
+        app.update();
+        assert_eq!(app.world.resource::<GlyphCalculatorCache>().0.len(), 1);
+    }
+
+    #[test]
+    fn click_picks_closest_boundary_in_line() {
+        let calculator = fira_sans();
+        let scale = PxScale::from(20.0);
+        let he = text_width("he", &calculator, scale);
+        let hel = text_width("hel", &calculator, scale);
+        let at = |x| {
+            cursor_index_at(
+                "hello",
+                &calculator,
+                scale,
+                &TextAlignment::default(),
+                Vec2::new(200.0, 30.0),
+                Vec2::new(x, 5.0),
+            )
+        };
+        assert_eq!(at(he + 0.1), 2);
+        assert_eq!(at(hel - 0.1), 3);
+        assert_eq!(at(-10.0), 0);
+        assert_eq!(at(1000.0), 5);
+    }
+
+    #[test]
+    fn click_counts_characters_not_bytes() {
+        let calculator = fira_sans();
+        let scale = PxScale::from(20.0);
+        let x = text_width("hé", &calculator, scale);
+        assert_eq!(
+            cursor_index_at(
+                "héllo",
+                &calculator,
+                scale,
+                &TextAlignment::default(),
+                Vec2::new(200.0, 30.0),
+                Vec2::new(x, 5.0)
+            ),
+            2
+        );
+    }
+
+    #[test]
+    fn click_picks_line_by_height() {
+        let calculator = fira_sans();
+        let scale = PxScale::from(20.0);
+        let line_height = line_height(&calculator.fonts()[0], scale);
+        let he = text_width("he", &calculator, scale);
+        let at = |y| {
+            cursor_index_at(
+                "hello\nhello",
+                &calculator,
+                scale,
+                &TextAlignment::default(),
+                Vec2::new(200.0, 60.0),
+                Vec2::new(he + 0.1, y),
+            )
+        };
+        assert_eq!(at(line_height * 0.5), 2);
+        assert_eq!(at(line_height * 1.5), 8);
+        // clicks above and below the text go to the first and the last line
+        assert_eq!(at(-line_height), 2);
+        assert_eq!(at(line_height * 10.0), 8);
+    }
+
+    #[test]
+    fn click_follows_text_alignment() {
+        let calculator = fira_sans();
+        let scale = PxScale::from(20.0);
+        let line_height = line_height(&calculator.fonts()[0], scale);
+        let size = Vec2::new(200.0, 100.0);
+        let centered = TextAlignment {
+            vertical: VerticalAlign::Center,
+            horizontal: HorizontalAlign::Center,
+        };
+        let value = "hello\nhi";
+        let at = |alignment: &TextAlignment, x, y| {
+            cursor_index_at(value, &calculator, scale, alignment, size, Vec2::new(x, y))
+        };
+        // the two lines are centered as a block, each line is centered on its own
+        let top = (size.y - 2.0 * line_height) / 2.0;
+        let hello_left = (size.x - text_width("hello", &calculator, scale)) / 2.0;
+        let hi_left = (size.x - text_width("hi", &calculator, scale)) / 2.0;
+        let he = text_width("he", &calculator, scale);
+        let h = text_width("h", &calculator, scale);
+        assert_eq!(
+            at(&centered, hello_left + he + 0.1, top + line_height * 0.5),
+            2
+        );
+        assert_eq!(at(&centered, hi_left + h + 0.1, top + line_height * 1.5), 7);
+        // the same x on top left text is past the end of the first line
+        assert_eq!(
+            at(
+                &TextAlignment::default(),
+                hello_left + he + 0.1,
+                line_height * 0.5
+            ),
+            5
+        );
+
+        let right = TextAlignment {
+            vertical: VerticalAlign::Top,
+            horizontal: HorizontalAlign::Right,
+        };
+        let hi_left = size.x - text_width("hi", &calculator, scale);
+        assert_eq!(at(&right, hi_left + h + 0.1, line_height * 1.5), 7);
+        assert_eq!(at(&right, hi_left - 20.0, line_height * 1.5), 6);
+    }
+
+    #[test]
+    fn fit_font_size_clamps_to_limits() {
+        let target_size = TextInputTargetSize {
+            size: Some(Size::new(200.0, 30.0)),
+            min_font_size: 10.0,
+            max_font_size: Some(16.0),
+        };
+        // fits, but is still capped at the maximum
+        assert_eq!(
+            target_size.fit_font_size(24.0, Vec2::new(100.0, 20.0)),
+            16.0
+        );
+        assert_eq!(
+            target_size.fit_font_size(24.0, Vec2::new(2000.0, 20.0)),
+            10.0
+        );
+        // without a target size only the limits apply
+        let limits = TextInputTargetSize {
+            size: None,
+            ..target_size
+        };
+        assert_eq!(limits.fit_font_size(24.0, Vec2::new(2000.0, 20.0)), 16.0);
+        assert_eq!(limits.fit_font_size(8.0, Vec2::new(2000.0, 20.0)), 10.0);
+    }
+
+    #[test]
+    fn fit_font_size_fits_the_tighter_dimension() {
+        let target_size = TextInputTargetSize::new(Size::new(200.0, 30.0));
+        // three lines are too tall, although they're narrow enough
+        assert_eq!(
+            target_size.fit_font_size(20.0, Vec2::new(100.0, 90.0)),
+            20.0 / 3.0
+        );
+        assert_eq!(target_size.fit_font_size(20.0, Vec2::new(800.0, 60.0)), 5.0);
+        assert_eq!(
+            target_size.fit_font_size(20.0, Vec2::new(200.0, 30.0)),
+            20.0
+        );
+    }
+
+    /// An app that runs only [`text_input_system`], with the resources and events it needs
+    fn text_input_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(bevy::asset::AssetPlugin)
+            .add_asset::<Font>()
+            .init_resource::<Input<KeyCode>>()
+            .init_resource::<TextInputKeybinds>()
+            .init_resource::<KeyRepeatConfig>()
+            .init_resource::<GlyphCalculatorCache>()
+            .init_resource::<TextInputClipboard>()
+            .add_event::<KeyboardInput>()
+            .add_event::<ReceivedCharacter>()
+            .add_event::<TextInputTruncated>()
+            .add_event::<TextInputRejected>()
+            .add_event::<TextInputSubmit>()
+            .add_system(text_input_system);
+        app
+    }
+
+    fn press_key(app: &mut App, key_code: KeyCode) {
+        app.world.send_event(KeyboardInput {
+            scan_code: 0,
+            key_code: Some(key_code),
+            state: ButtonState::Pressed,
+        });
+        app.update();
+    }
+
+    /// Types `text` one character per frame
+    fn type_text(app: &mut App, text: &str) {
+        for ch in text.chars() {
+            app.world.send_event(ReceivedCharacter {
+                id: bevy::window::WindowId::primary(),
+                char: ch,
+            });
+            app.update();
+        }
+    }
+
+    #[test]
+    fn typing_works_without_a_font() {
+        let mut app = text_input_app();
+        let entity = app
+            .world
+            .spawn()
+            .insert_bundle(crate::TextInputBundle {
+                focus: TextInputFocus(Some(0)),
+                ..Default::default()
+            })
+            .id();
+
+        type_text(&mut app, "hi");
+        // moving between lines needs a font, so it's skipped
+        press_key(&mut app, KeyCode::Up);
+        press_key(&mut app, KeyCode::Left);
+        type_text(&mut app, "!");
+        assert_eq!(app.world.get::<TextInputValue>(entity).unwrap().0, "h!i");
+        assert_eq!(app.world.get::<TextInputFocus>(entity).unwrap().0, Some(2));
+    }
+
+    #[test]
+    fn typing_after_moving_left_inserts_between_characters() {
+        let mut app = text_input_app();
+        let entity = app
+            .world
+            .spawn()
+            .insert(InputTextStyle::default())
+            .insert(TextInputValue::default())
+            .insert(TextInputFocus(Some(0)))
+            .insert(TextInputConstrains::default())
+            .insert(InputMode::SingleLine)
+            .id();
+
+        type_text(&mut app, "café");
+        assert_eq!(app.world.get::<TextInputFocus>(entity).unwrap().0, Some(4));
+        press_key(&mut app, KeyCode::Left);
+        press_key(&mut app, KeyCode::Left);
+        type_text(&mut app, "x");
+        assert_eq!(app.world.get::<TextInputValue>(entity).unwrap().0, "caxfé");
+        assert_eq!(app.world.get::<TextInputFocus>(entity).unwrap().0, Some(3));
+    }
+
+    /// Entities of the focus events sent since the last call, blurred and focused ones
+    fn focus_events(
+        app: &App,
+        blurred: &mut ManualEventReader<TextInputBlurred>,
+        focused: &mut ManualEventReader<TextInputFocused>,
+    ) -> (Vec<Entity>, Vec<Entity>) {
+        let blurred = blurred
+            .iter(app.world.resource::<Events<TextInputBlurred>>())
+            .map(|event| event.entity)
+            .collect();
+        let focused = focused
+            .iter(app.world.resource::<Events<TextInputFocused>>())
+            .map(|event| event.entity)
+            .collect();
+        (blurred, focused)
+    }
+
+    #[test]
+    fn smooth_blink_fades_cursor_alpha() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .add_system(text_input_blink_cursor_system);
+        let input = app
+            .world
+            .spawn()
+            .insert(TextCursorStyle::default(
+                24.0,
+                Color::WHITE.into(),
+                UiRect::default(),
+                TextAlignment::default(),
+            ))
+            .insert(CursorBlink::Smooth)
+            .id();
+        let cursor = app
+            .world
+            .spawn()
+            .insert(TextCursor)
+            .insert(Visibility::default())
+            .insert(BlinkingTimer(Timer::new(Duration::from_millis(1000), true)))
+            .insert(UiColor(Color::WHITE))
+            .id();
+        app.world.entity_mut(input).push_children(&[cursor]);
+        let start = Instant::now();
+        let alpha_at = |app: &mut App, millis| {
+            app.world
+                .resource_mut::<Time>()
+                .update_with_instant(start + Duration::from_millis(millis));
+            app.update();
+            app.world.get::<UiColor>(cursor).unwrap().0.a()
+        };
+
+        alpha_at(&mut app, 0);
+        let alphas = [250, 500, 750].map(|millis| alpha_at(&mut app, millis));
+        assert!((alphas[0] - 0.5).abs() < 0.01, "{alphas:?}");
+        assert!(alphas[1] < 0.01, "{alphas:?}");
+        assert!((alphas[2] - 0.5).abs() < 0.01, "{alphas:?}");
+        // the cursor stays visible, only its color fades
+        assert!(app.world.get::<Visibility>(cursor).unwrap().is_visible);
+    }
+
+    #[test]
+    fn moving_focus_blurs_old_input_and_focuses_new_one() {
+        let mut app = App::new();
+        app.add_event::<TextInputFocused>()
+            .add_event::<TextInputBlurred>()
+            .add_system(text_input_focus_event_system);
+        let a = app.world.spawn().insert(TextInputFocus(Some(0))).id();
+        let b = app.world.spawn().insert(TextInputFocus(None)).id();
+        let (mut blurred, mut focused) =
+            (ManualEventReader::default(), ManualEventReader::default());
+        app.update();
+        assert_eq!(
+            focus_events(&app, &mut blurred, &mut focused),
+            (vec![], vec![])
+        );
+
+        app.world.get_mut::<TextInputFocus>(a).unwrap().0 = None;
+        app.world.get_mut::<TextInputFocus>(b).unwrap().0 = Some(0);
+        app.update();
+        assert_eq!(
+            focus_events(&app, &mut blurred, &mut focused),
+            (vec![a], vec![b])
+        );
+    }
+
+    #[test]
+    fn clicking_focused_input_keeps_focus_without_events() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(bevy::asset::AssetPlugin)
+            .add_asset::<Font>()
+            .init_resource::<Input<MouseButton>>()
+            .init_resource::<Windows>()
+            .init_resource::<GlyphCalculatorCache>()
+            .init_resource::<MultiClickInterval>()
+            .add_event::<TextInputFocused>()
+            .add_event::<TextInputBlurred>()
+            .add_system(text_input_unfocus_system.before(text_input_focus_on_click_system))
+            .add_system(text_input_focus_on_click_system)
+            .add_system(text_input_focus_event_system.after(text_input_focus_on_click_system));
+        let entity = app
+            .world
+            .spawn()
+            .insert(InputTextStyle::default())
+            .insert(TextInputValue("hello".to_string()))
+            .insert(TextInputFocus(Some(2)))
+            .insert(Interaction::None)
+            .id();
+        let (mut blurred, mut focused) =
+            (ManualEventReader::default(), ManualEventReader::default());
+        app.update();
+
+        for _ in 0..2 {
+            let mut mouse = app.world.resource_mut::<Input<MouseButton>>();
+            mouse.release(MouseButton::Left);
+            mouse.clear();
+            mouse.press(MouseButton::Left);
+            *app.world.get_mut::<Interaction>(entity).unwrap() = Interaction::Clicked;
+            app.update();
+            assert!(app.world.get::<TextInputFocus>(entity).unwrap().0.is_some());
+            assert_eq!(
+                focus_events(&app, &mut blurred, &mut focused),
+                (vec![], vec![])
+            );
+        }
+        // both clicks reached the input, as a double click
+        assert_eq!(app.world.get::<TextInputClicks>(entity).unwrap().count, 2);
+    }
+
+    #[test]
+    fn shift_right_extends_selection() {
+        let mut app = text_input_app();
+        app.world
+            .resource_mut::<Input<KeyCode>>()
+            .press(KeyCode::LShift);
+        let entity = app
+            .world
+            .spawn()
+            .insert(InputTextStyle::default())
+            .insert(TextInputValue("héllo".to_string()))
+            .insert(TextInputFocus(Some(0)))
+            .insert(TextInputConstrains::default())
+            .insert(InputMode::SingleLine)
+            .insert(TextInputSelection::default())
+            .id();
+
+        for _ in 0..3 {
+            press_key(&mut app, KeyCode::Right);
+        }
+        assert_eq!(app.world.get::<TextInputFocus>(entity).unwrap().0, Some(3));
+        let selection = app.world.get::<TextInputSelection>(entity).unwrap();
+        assert_eq!(selection.anchor, Some(0));
+        assert_eq!(selection.range(3), Some(0..3));
+        assert_eq!(app.world.get::<TextInputValue>(entity).unwrap().0, "héllo");
+    }
+
+    #[test]
+    fn events_in_one_frame_apply_in_fixed_order() {
+        let mut app = text_input_app();
+        let entity = app
+            .world
+            .spawn()
+            .insert(InputTextStyle::default())
+            .insert(TextInputValue("hello".to_string()))
+            .insert(TextInputFocus(Some(5)))
+            .insert(TextInputConstrains::default())
+            .insert(InputMode::SingleLine)
+            .id();
+
+        // arriving as typing, backspace, then left, but applied as backspace, left, typing
+        for ch in ['x', '\u{8}'] {
+            app.world.send_event(ReceivedCharacter {
+                id: bevy::window::WindowId::primary(),
+                char: ch,
+            });
+        }
+        press_key(&mut app, KeyCode::Left);
+        assert_eq!(app.world.get::<TextInputValue>(entity).unwrap().0, "helxl");
+        assert_eq!(app.world.get::<TextInputFocus>(entity).unwrap().0, Some(4));
+    }
+
+    #[test]
+    fn overtype_replaces_characters_after_cursor() {
+        let mut app = text_input_app();
+        let entity = app
+            .world
+            .spawn()
+            .insert(InputTextStyle::default())
+            .insert(TextInputValue("abc".to_string()))
+            .insert(TextInputFocus(Some(0)))
+            .insert(TextInputConstrains::default())
+            .insert(InputMode::SingleLine)
+            .insert(TextInputOvertype::default())
+            .id();
+
+        press_key(&mut app, KeyCode::Insert);
+        assert!(app.world.get::<TextInputOvertype>(entity).unwrap().0);
+        type_text(&mut app, "xy");
+        assert_eq!(app.world.get::<TextInputValue>(entity).unwrap().0, "xyc");
+        assert_eq!(app.world.get::<TextInputFocus>(entity).unwrap().0, Some(2));
+        // past the end, characters are appended
+        type_text(&mut app, "zw");
+        assert_eq!(app.world.get::<TextInputValue>(entity).unwrap().0, "xyzw");
+
+        press_key(&mut app, KeyCode::Insert);
+        app.world.get_mut::<TextInputFocus>(entity).unwrap().0 = Some(0);
+        type_text(&mut app, "!");
+        assert_eq!(app.world.get::<TextInputValue>(entity).unwrap().0, "!xyzw");
+    }
+
+    #[test]
+    fn read_only_input_ignores_typing() {
+        let mut app = text_input_app();
+        let entity = app
+            .world
+            .spawn()
+            .insert(InputTextStyle::default())
+            .insert(TextInputValue("hello".to_string()))
+            .insert(TextInputFocus(Some(5)))
+            .insert(TextInputConstrains::default())
+            .insert(InputMode::SingleLine)
+            .insert(TextInputStatus::ReadOnly)
+            .id();
+
+        type_text(&mut app, "abc\u{8}");
+        assert_eq!(app.world.get::<TextInputValue>(entity).unwrap().0, "hello");
+        assert_eq!(app.world.get::<TextInputFocus>(entity).unwrap().0, Some(5));
+        // but the cursor still moves
+        press_key(&mut app, KeyCode::Left);
+        assert_eq!(app.world.get::<TextInputFocus>(entity).unwrap().0, Some(4));
+    }
+
+    #[test]
+    fn locked_input_ignores_typing() {
+        let mut app = text_input_app();
+        let entity = app
+            .world
+            .spawn()
+            .insert(InputTextStyle::default())
+            .insert(TextInputValue("hello".to_string()))
+            .insert(TextInputFocus(Some(5)))
+            .insert(TextInputConstrains::default())
+            .insert(InputMode::SingleLine)
+            .insert(InputLocked)
+            .id();
+
+        type_text(&mut app, "abc\u{8}");
+        assert_eq!(app.world.get::<TextInputValue>(entity).unwrap().0, "hello");
+
+        app.world.entity_mut(entity).remove::<InputLocked>();
+        type_text(&mut app, "!");
+        assert_eq!(app.world.get::<TextInputValue>(entity).unwrap().0, "hello!");
+    }
+
+    #[test]
+    fn status_change_clears_selection() {
+        let mut app = App::new();
+        app.add_system(text_input_status_system);
+        let spawn = |app: &mut App| {
+            app.world
+                .spawn()
+                .insert(TextInputStatus::Enabled)
+                .insert(TextInputFocus(Some(5)))
+                .insert(TextInputSelection { anchor: Some(0) })
+                .insert(UiColor::default())
+                .id()
+        };
+        let (read_only, disabled) = (spawn(&mut app), spawn(&mut app));
+        app.update();
+        assert_eq!(
+            app.world
+                .get::<TextInputSelection>(read_only)
+                .unwrap()
+                .anchor,
+            Some(0)
+        );
+
+        *app.world.get_mut::<TextInputStatus>(read_only).unwrap() = TextInputStatus::ReadOnly;
+        *app.world.get_mut::<TextInputStatus>(disabled).unwrap() = TextInputStatus::Disabled;
+        app.update();
+        assert_eq!(
+            app.world
+                .get::<TextInputSelection>(read_only)
+                .unwrap()
+                .anchor,
+            None
+        );
+        assert_eq!(
+            app.world.get::<TextInputFocus>(read_only).unwrap().0,
+            Some(5)
+        );
+        assert_eq!(
+            app.world
+                .get::<TextInputSelection>(disabled)
+                .unwrap()
+                .anchor,
+            None
+        );
+        assert_eq!(app.world.get::<TextInputFocus>(disabled).unwrap().0, None);
+    }
+
+    #[test]
+    fn ctrl_backspace_and_ctrl_delete_remove_words() {
+        let mut app = text_input_app();
+        app.world
+            .resource_mut::<Input<KeyCode>>()
+            .press(KeyCode::LControl);
+        let entity = app
+            .world
+            .spawn()
+            .insert(InputTextStyle::default())
+            .insert(TextInputValue("hello big  world".to_string()))
+            .insert(TextInputFocus(Some(16)))
+            .insert(TextInputConstrains::default())
+            .insert(InputMode::SingleLine)
+            .id();
+
+        press_key(&mut app, KeyCode::Back);
+        assert_eq!(
+            app.world.get::<TextInputValue>(entity).unwrap().0,
+            "hello big  "
+        );
+        assert_eq!(app.world.get::<TextInputFocus>(entity).unwrap().0, Some(11));
+
+        app.world.get_mut::<TextInputFocus>(entity).unwrap().0 = Some(0);
+        press_key(&mut app, KeyCode::Delete);
+        assert_eq!(app.world.get::<TextInputValue>(entity).unwrap().0, " big  ");
+        assert_eq!(app.world.get::<TextInputFocus>(entity).unwrap().0, Some(0));
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn copy_and_paste_round_trip_through_in_memory_clipboard() {
+        let mut app = text_input_app();
+        app.insert_resource(TextInputClipboard(Box::new(InMemoryClipboard::default())));
+        let entity = app
+            .world
+            .spawn()
+            .insert(InputTextStyle::default())
+            .insert(TextInputValue("hello".to_string()))
+            .insert(TextInputFocus(Some(5)))
+            .insert(TextInputConstrains::default())
+            .insert(InputMode::SingleLine)
+            .insert(TextInputSelection { anchor: Some(0) })
+            .id();
+        let type_char = |app: &mut App, ch| {
+            app.world.send_event(ReceivedCharacter {
+                id: bevy::window::WindowId::primary(),
+                char: ch,
+            });
+            app.update();
+        };
+
+        // Ctrl-C
+        type_char(&mut app, '\u{3}');
+        let mut clipboard = app.world.resource_mut::<TextInputClipboard>();
+        assert_eq!(clipboard.0.get_contents().as_deref(), Some("hello"));
+
+        app.world
+            .get_mut::<TextInputSelection>(entity)
+            .unwrap()
+            .anchor = None;
+        // Ctrl-V
+        type_char(&mut app, '\u{16}');
+        assert_eq!(
+            app.world.get::<TextInputValue>(entity).unwrap().0,
+            "hellohello"
+        );
+        assert_eq!(app.world.get::<TextInputFocus>(entity).unwrap().0, Some(10));
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn cut_and_paste_round_trip_through_in_memory_clipboard() {
+        let mut app = text_input_app();
+        app.insert_resource(TextInputClipboard(Box::new(InMemoryClipboard::default())));
+        let entity = app
+            .world
+            .spawn()
+            .insert(InputTextStyle::default())
+            .insert(TextInputValue("hello world".to_string()))
+            .insert(TextInputFocus(Some(5)))
+            .insert(TextInputConstrains::default())
+            .insert(InputMode::SingleLine)
+            .insert(TextInputSelection { anchor: Some(0) })
+            .id();
+
+        // Ctrl-X
+        type_text(&mut app, "\u{18}");
+        assert_eq!(app.world.get::<TextInputValue>(entity).unwrap().0, " world");
+        assert_eq!(app.world.get::<TextInputFocus>(entity).unwrap().0, Some(0));
+        assert_eq!(
+            app.world.get::<TextInputSelection>(entity).unwrap().anchor,
+            None
+        );
+
+        app.world.get_mut::<TextInputFocus>(entity).unwrap().0 = Some(6);
+        // Ctrl-V
+        type_text(&mut app, "\u{16}");
+        assert_eq!(
+            app.world.get::<TextInputValue>(entity).unwrap().0,
+            " worldhello"
+        );
+        assert_eq!(app.world.get::<TextInputFocus>(entity).unwrap().0, Some(11));
+    }
+
+    #[test]
+    fn word_bounds_pick_the_run_around_index() {
+        assert_eq!(word_bounds("hello, world", 2), (0, 5));
+        assert_eq!(word_bounds("hello, world", 5), (5, 6));
+        assert_eq!(word_bounds("hello, world", 6), (6, 7));
+        assert_eq!(word_bounds("héllo wörld", 8), (6, 11));
+        assert_eq!(word_bounds("", 0), (0, 0));
+    }
+
+    #[test]
+    fn word_bounds_at_line_end_use_the_word_before() {
+        assert_eq!(word_bounds("hello, world", 12), (7, 12));
+        assert_eq!(word_bounds("ab\ncd", 2), (0, 2));
+        // an empty line has no word
+        assert_eq!(word_bounds("a\n\nb", 2), (2, 2));
+    }
+
+    #[test]
+    fn line_bounds_exclude_line_breaks() {
+        assert_eq!(line_bounds("ab\ncdé\nf", 0), (0, 2));
+        assert_eq!(line_bounds("ab\ncdé\nf", 4), (3, 6));
+        assert_eq!(line_bounds("ab\ncdé\nf", 8), (7, 8));
+        assert_eq!(line_bounds("ab\n\ncd", 3), (3, 3));
+    }
+
+    #[test]
+    fn prev_word_boundary_skips_whitespace() {
+        let text = "hello big  world";
+        assert_eq!(prev_word_boundary(text, 16), 11);
+        assert_eq!(prev_word_boundary(text, 11), 6);
+        assert_eq!(prev_word_boundary(text, 6), 0);
+        assert_eq!(prev_word_boundary(text, 0), 0);
+        assert_eq!(prev_word_boundary("añb c", 5), 0);
+    }
+
+    #[test]
+    fn next_word_boundary_skips_whitespace() {
+        let text = "hello big  world";
+        assert_eq!(next_word_boundary(text, 0), 5);
+        assert_eq!(next_word_boundary(text, 5), 9);
+        assert_eq!(next_word_boundary(text, 9), 16);
+        assert_eq!(next_word_boundary(text, 16), 16);
+        assert_eq!(next_word_boundary("añb c", 0), 4);
+    }
+
+    #[test]
+    fn shorter_value_clamps_cursor_and_anchor() {
+        let mut app = App::new();
+        app.add_system(text_input_clamp_cursor_system);
+        let entity = app
+            .world
+            .spawn()
+            .insert(TextInputValue("hello world".to_string()))
+            .insert(TextInputFocus(Some(11)))
+            .insert(TextInputSelection { anchor: Some(8) })
+            .id();
+        let unfocused = app
+            .world
+            .spawn()
+            .insert(TextInputValue("hello".to_string()))
+            .insert(TextInputFocus(None))
+            .id();
+        app.update();
+
+        app.world.get_mut::<TextInputValue>(entity).unwrap().0 = "héllo".to_string();
+        app.world
+            .get_mut::<TextInputValue>(unfocused)
+            .unwrap()
+            .0
+            .clear();
+        app.update();
+        assert_eq!(app.world.get::<TextInputFocus>(entity).unwrap().0, Some(5));
+        assert_eq!(
+            app.world.get::<TextInputSelection>(entity).unwrap().anchor,
+            Some(5)
+        );
+        assert_eq!(app.world.get::<TextInputFocus>(unfocused).unwrap().0, None);
+    }
+
+    #[test]
+    fn move_lines_keeps_column() {
+        assert_eq!(move_lines("abc\nde\nfghi", 2, 1), 6);
+        assert_eq!(move_lines("abc\nde\nfghi", 6, 1), 9);
+        assert_eq!(move_lines("abc\nde\nfghi", 9, -2), 2);
+        assert_eq!(move_lines("abc\nde\nfghi", 1, -1), 1);
+    }
+
+    #[test]
+    fn move_lines_clamps_cursor_past_end() {
+        assert_eq!(move_lines("ab\ncd", 40, -1), 2);
+        assert_eq!(move_lines("", 3, 1), 0);
+    }
+
+    #[test]
+    fn default_cursor_is_centered_on_vertically_centered_line() {
+        let (box_height, font_size) = (60.0, 24.0);
+        let alignment = TextAlignment {
+            vertical: VerticalAlign::Center,
+            horizontal: HorizontalAlign::Left,
+        };
+        let cursor_style = TextCursorStyle::default(
+            font_size,
+            Color::BLACK.into(),
+            UiRect::all(Val::Px(4.0)),
+            alignment,
+        );
+        let style = &cursor_style.0.style;
+        assert_eq!(
+            (style.margin.top, style.margin.bottom),
+            (Val::Auto, Val::Auto)
+        );
+
+        let line_height = line_height(&fira_sans().fonts()[0], PxScale::from(font_size));
+        let anchor = cursor_anchor(style);
+        // auto margins split the free space of the box evenly above and below the cursor
+        let cursor_top =
+            (box_height - font_size) / 2.0 + line_top("hello", 5, anchor, line_height, 0.0);
+        let text_top = line_top_in_view(
+            "hello",
+            5,
+            text_anchor(&alignment),
+            line_height,
+            box_height,
+            0.0,
+        );
+        assert!(
+            (cursor_top + font_size / 2.0 - (text_top + line_height / 2.0)).abs() < 0.01,
+            "cursor at {cursor_top}, line at {text_top}"
+        );
+        assert!((cursor_top - 18.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn cursor_after_trailing_new_line_is_on_the_empty_last_line() {
+        let value = "a\nb\n";
+        let end = value.len();
+        assert_eq!(cursor_line_offset(value, end, 0.0), 2.0);
+        assert_eq!(cursor_line_offset(value, end, 0.5), 1.0);
+        assert_eq!(cursor_line_offset(value, end, 1.0), 0.0);
+        assert_eq!(line_top(value, end, 0.0, 10.0, 5.0), 25.0);
+        assert_eq!(line_top(value, end, 1.0, 10.0, 5.0), 5.0);
+        // the line before the trailing new line is one line higher
+        assert_eq!(line_top(value, end - 1, 1.0, 10.0, 5.0), -5.0);
+    }
+
+    #[test]
+    fn scroll_follows_cursor_for_every_alignment() {
+        // 10 lines of 10px in a 30px view, the cursor on the last line
+        let value = "0\n1\n2\n3\n4\n5\n6\n7\n8\n9";
+        let (line_height, visible_height) = (10.0, 30.0);
+        let last_line = value.len();
+        for (anchor, expected) in [(0.0, -70.0), (0.5, -35.0), (1.0, 0.0)] {
+            let top = line_top_in_view(value, last_line, anchor, line_height, visible_height, 0.0);
+            let offset = scroll_into_view(0.0, top, line_height, visible_height);
+            let (min, max) = scroll_range(100.0, visible_height, anchor);
+            assert_eq!(offset.clamp(min, max), expected, "anchor {anchor}");
+        }
+        // and back to the first line, from the view at the last lines
+        for anchor in [0.0, 0.5, 1.0] {
+            let (min, max) = scroll_range(100.0, visible_height, anchor);
+            let offset = min;
+            let top = line_top_in_view(value, 0, anchor, line_height, visible_height, offset);
+            assert!(top < 0.0, "anchor {anchor}");
+            let offset = scroll_into_view(offset, top, line_height, visible_height);
+            assert_eq!(offset.clamp(min, max), max, "anchor {anchor}");
+        }
+    }
+
+    #[test]
+    fn scroll_range_is_empty_when_text_fits() {
+        assert_eq!(scroll_range(20.0, 30.0, 0.0), (0.0, 0.0));
+        assert_eq!(scroll_range(20.0, 30.0, 1.0), (0.0, 0.0));
+    }
 }
@@ -0,0 +1,92 @@
+//! Reveals a text input's value one character at a time, e.g. for dialogue boxes.
+
+use bevy::prelude::*;
+
+use crate::text_input::{InputLocked, TextInputFocus, TextInputValue};
+
+/// Replaces the value of a text input with `target`, revealing it one character at a time.
+/// The input is [`InputLocked`] until the whole `target` is shown, then this component is removed
+#[derive(Component, Clone, Debug)]
+pub struct TypewriterReveal {
+    /// The text to reveal
+    pub target: String,
+    /// How fast to reveal it. Zero or less reveals everything at once
+    pub chars_per_sec: f32,
+}
+
+/// Inserted by [`typewriter_reveal_system`] while [`TypewriterReveal`] is in progress
+#[derive(Component, Debug)]
+pub struct TypewriterProgress {
+    elapsed: f32,
+    was_locked: bool,
+}
+
+/// Sent when [`TypewriterReveal`] has shown its whole target
+#[derive(Clone, Copy, Debug)]
+pub struct TypewriterFinished {
+    /// The text input entity
+    pub entity: Entity,
+}
+
+pub fn typewriter_reveal_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(
+        Entity,
+        &TypewriterReveal,
+        Option<&mut TypewriterProgress>,
+        &mut TextInputValue,
+        &mut TextInputFocus,
+        Option<&InputLocked>,
+    )>,
+    mut finished: EventWriter<TypewriterFinished>,
+) {
+    for (entity, reveal, progress, mut value, mut focus, locked) in query.iter_mut() {
+        let mut progress = match progress {
+            Some(progress) => progress,
+            None => {
+                commands
+                    .entity(entity)
+                    .insert(TypewriterProgress {
+                        elapsed: 0.0,
+                        was_locked: locked.is_some(),
+                    })
+                    .insert(InputLocked);
+                if !value.is_empty() {
+                    value.0.clear();
+                }
+                if let Some(cursor) = focus.0.as_mut() {
+                    *cursor = 0;
+                }
+                continue;
+            }
+        };
+        progress.elapsed += time.delta_seconds();
+        let revealed = if reveal.chars_per_sec > 0.0 {
+            (progress.elapsed * reveal.chars_per_sec) as usize
+        } else {
+            usize::MAX
+        };
+        let end = reveal
+            .target
+            .char_indices()
+            .nth(revealed)
+            .map_or(reveal.target.len(), |(i, _)| i);
+        if value.0 != reveal.target[..end] {
+            value.0 = reveal.target[..end].to_string();
+            if let Some(cursor) = focus.0.as_mut() {
+                *cursor = revealed.min(reveal.target.chars().count());
+            }
+        }
+        if end == reveal.target.len() {
+            let mut entity_commands = commands.entity(entity);
+            entity_commands
+                .remove::<TypewriterReveal>()
+                .remove::<TypewriterProgress>();
+            if !progress.was_locked {
+                entity_commands.remove::<InputLocked>();
+            }
+            finished.send(TypewriterFinished { entity });
+        }
+    }
+}
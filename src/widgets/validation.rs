@@ -0,0 +1,81 @@
+//! Validation that can read resources, e.g. "this username is already taken".
+//!
+//! Unlike [`TextInputConstrains`](crate::text_input::TextInputConstrains), validators don't block
+//! edits. The value is accepted as typed, and validators only report whether it's valid through
+//! [`ValidationState`], so the app can show an error or disable a submit button.
+
+use bevy::prelude::*;
+
+use crate::text_input::TextInputValue;
+use crate::{SlimyWidgetsSet, SystemLabels};
+
+/// Result of the validators of a text input. Reset to [`ValidationState::Unchecked`]
+/// whenever the value changes, then set by validators added with
+/// [`TextInputValidationAppExt::add_text_input_validator`]
+#[derive(Component, Clone, Debug, PartialEq, Eq, Default)]
+pub enum ValidationState {
+    /// No validator has looked at the current value yet
+    #[default]
+    Unchecked,
+    /// The value is valid
+    Valid,
+    /// The value is invalid, with a message for the user
+    Invalid(String),
+}
+
+/// Registers validator systems
+pub trait TextInputValidationAppExt {
+    /// Adds a system that sets [`ValidationState`] of text inputs. It runs after the value is
+    /// edited and the state is reset, so it should only look at inputs with
+    /// `Changed<TextInputValue>` or [`ValidationState::Unchecked`].
+    /// ```no_run
+    /// # use bevy::prelude::*;
+    /// # use bevy_slimy_widgets::text_input::TextInputValue;
+    /// # use bevy_slimy_widgets::validation::{TextInputValidationAppExt, ValidationState};
+    /// struct TakenUsernames(Vec<String>);
+    ///
+    /// fn username_validator(
+    ///     taken: Res<TakenUsernames>,
+    ///     mut query: Query<(&TextInputValue, &mut ValidationState), Changed<TextInputValue>>,
+    /// ) {
+    ///     for (value, mut state) in query.iter_mut() {
+    ///         *state = if taken.0.iter().any(|name| *name == value.0) {
+    ///             ValidationState::Invalid("This username is already taken".to_string())
+    ///         } else {
+    ///             ValidationState::Valid
+    ///         };
+    ///     }
+    /// }
+    ///
+    /// App::new().add_text_input_validator(username_validator);
+    /// ```
+    fn add_text_input_validator<Params>(
+        &mut self,
+        validator: impl IntoSystem<(), (), Params>,
+    ) -> &mut Self;
+}
+
+impl TextInputValidationAppExt for App {
+    fn add_text_input_validator<Params>(
+        &mut self,
+        validator: impl IntoSystem<(), (), Params>,
+    ) -> &mut Self {
+        self.add_system(
+            validator
+                .label(SystemLabels::TextInputValidate)
+                .label(SlimyWidgetsSet::TextInput)
+                .after(SystemLabels::TextInputValidationReset),
+        )
+    }
+}
+
+/// Resets [`ValidationState`] when the value changes
+pub fn text_input_validation_reset_system(
+    mut query: Query<&mut ValidationState, Changed<TextInputValue>>,
+) {
+    for mut state in query.iter_mut() {
+        if *state != ValidationState::Unchecked {
+            *state = ValidationState::Unchecked;
+        }
+    }
+}